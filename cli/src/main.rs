@@ -4,24 +4,38 @@ use anyhow::Result;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let choices = vec!["new", "dev", "edit", "quit"];
-    let selection = Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
-        .with_prompt("Choose command")
-        .items(&choices)
-        .default(0)
-        .interact()?;
+    let choices = vec!["new", "dev", "edit", "routes", "quit"];
+    // `cli <command>` runs it directly, plain `cli` asks
+    let command = match std::env::args().nth(1) {
+        Some(arg) if choices.contains(&arg.as_str()) => arg,
+        Some(arg) => anyhow::bail!("unknown command `{}` (expected one of: {})", arg, choices.join(", ")),
+        None => {
+            let selection = Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                .with_prompt("Choose command")
+                .items(&choices)
+                .default(0)
+                .interact()?;
+            choices[selection].to_string()
+        }
+    };
 
-    match choices[selection] {
-        "new" => cmd_new().await?,
+    match command.as_str() {
+        "new" => cmd_new()?,
         "dev" => cmd_dev().await?,
         "edit" => cmd_edit()?,
+        "routes" => cmd_routes()?,
         _ => println!("bye"),
     }
 
     Ok(())
 }
 
-async fn cmd_new() -> Result<()> {
+/// Where new projects get the framework from. A project only holds its own code, so upgrading
+/// it is bumping the `rsf` and `rsf-build` versions in its Cargo.toml.
+const RSF_GIT: &str = "https://github.com/TheMaxerius/framework_test";
+const RSF_VERSION: &str = "0.1";
+
+fn cmd_new() -> Result<()> {
     let project_name: String = Input::new().with_prompt("Project name").interact_text()?;
     let project_root = PathBuf::from(format!("./{}", project_name));
     
@@ -30,116 +44,47 @@ async fn cmd_new() -> Result<()> {
         return Ok(());
     }
 
-    println!("Downloading template...");
-    
-    // Download the template tarball from GitHub
-    let template_url = "https://github.com/TheMaxerius/framework_test/archive/refs/heads/master.tar.gz";
-    let response = reqwest::Client::new()
-        .get(template_url)
-        .send()
-        .await?;
-    
-    if !response.status().is_success() {
-        anyhow::bail!("Failed to download template: {}", response.status());
-    }
-    
-    let bytes = response.bytes().await?;
-    
-    // Extract to temp directory
-    let temp_dir = std::env::temp_dir().join(format!("framework_template_{}", std::process::id()));
-    if temp_dir.exists() {
-        std::fs::remove_dir_all(&temp_dir)?;
-    }
-    std::fs::create_dir_all(&temp_dir)?;
-    
-    // Decompress and extract tar.gz
-    let cursor = std::io::Cursor::new(bytes);
-    let gz = flate2::read::GzDecoder::new(cursor);
-    let mut archive = tar::Archive::new(gz);
-    archive.unpack(&temp_dir)?;
-    
-    // Find the extracted folder (could be framework_test-master or framework-template-main, etc)
-    let mut entries: Vec<_> = std::fs::read_dir(&temp_dir)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_dir())
-        .collect();
-    
-    if entries.is_empty() {
-        println!("Temp dir contents:");
-        for entry in std::fs::read_dir(&temp_dir)? {
-            if let Ok(e) = entry {
-                println!("  {:?}", e.path());
-            }
-        }
-        anyhow::bail!("No directories found in extracted template");
-    }
-    
-    let extracted_dir = entries.remove(0).path();
-    println!("Using template from: {:?}", extracted_dir);
-    
-    // Copy extracted template to project root
-    std::fs::create_dir_all(&project_root)?;
-    copy_dir_all(&extracted_dir, &project_root)?;
-    
-    println!("Created project {} at {:?}", project_name, project_root);
-    
-    // Create user src directory
-    let user_src_dir = project_root.join("src");
-    std::fs::create_dir_all(&user_src_dir)?;
-    
-    // Create example main.rs
-    let main_rs_content = r#"//'api'
-use std::collections::HashMap;
+    let cargo_toml = format!(r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2024"
 
-// Handler for GET /main
-pub fn GET(_params: &HashMap<String, String>) -> String {
-    "Hello from example main".to_string()
-}"#;
-    std::fs::write(user_src_dir.join("main.rs"), main_rs_content)?;
-    println!("Created user src directory");
-    
-    // Update project.json with correct path
-    let pj_path = project_root.join("core/src/engine/project.json");
-    if pj_path.exists() {
-        let mut pj: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&pj_path)?)?;
-        pj["parent_folder"] = serde_json::Value::String(format!("../../../{}", project_name));
-        std::fs::write(&pj_path, serde_json::to_string_pretty(&pj)?)?;
+[dependencies]
+rsf = {{ git = "{git}", version = "{version}" }}
+
+[build-dependencies]
+rsf-build = {{ git = "{git}", version = "{version}" }}
+"#, name = project_name, git = RSF_GIT, version = RSF_VERSION);
+    let project_json = serde_json::to_string_pretty(&serde_json::json!({ "name": project_name, "version": "0.1.0" }))?;
+    let files = [
+        ("Cargo.toml", cargo_toml),
+        ("build.rs", "fn main() {\n    rsf_build::generate(\"routes\");\n}\n".to_string()),
+        ("project.json", project_json + "\n"),
+        ("src/main.rs", "rsf::main!();\n".to_string()),
+        ("routes/index.rs", "// Handler for GET /\npub fn GET() -> &'static str {\n    \"Hello from RSF\"\n}\n".to_string()),
+        (".gitignore", "/target\n".to_string()),
+    ];
+    for (path, content) in &files {
+        let path = project_root.join(path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, content)?;
     }
     
-    // Cleanup
-    std::fs::remove_dir_all(&temp_dir)?;
-    
     println!("\n✓ Project created successfully!");
     println!("\nNext steps:");
     println!("1. cd {}", project_name);
-    println!("2. Run 'cli' and select 'edit' to configure the project");
+    println!("2. Add route files under routes/ (e.g. routes/users/[id].rs)");
     println!("3. Run 'cli' and select 'dev' to start the development server");
     
     Ok(())
 }
 
-fn copy_dir_all(src: &PathBuf, dst: &PathBuf) -> Result<()> {
-    std::fs::create_dir_all(&dst)?;
-    for entry in std::fs::read_dir(src)? {
-        let entry = entry?;
-        let ty = entry.file_type()?;
-        let src_path = entry.path();
-        let file_name = entry.file_name();
-        let dst_path = dst.join(&file_name);
-
-        if ty.is_dir() {
-            copy_dir_all(&src_path, &dst_path)?;
-        } else {
-            std::fs::copy(&src_path, &dst_path)?;
-        }
-    }
-    Ok(())
-}
-
 async fn cmd_dev() -> Result<()> {
-    println!("Starting dev server in core/");
+    println!("Starting dev server");
     let mut cmd = tokio::process::Command::new("cargo");
-    cmd.arg("run").current_dir("core");
+    cmd.arg("run");
     let status = cmd.status().await?;
     println!("cargo run exited with {}", status);
     Ok(())
@@ -147,14 +92,61 @@ async fn cmd_dev() -> Result<()> {
 
 fn cmd_edit() -> Result<()> {
     let parent: String = Input::new()
-        .with_prompt("New parent_folder for project.json (relative to core/src/engine)")
+        .with_prompt("New parent_folder for project.json (relative to the project root)")
         .interact_text()?;
 
-    let pj = PathBuf::from("core/src/engine/project.json");
-    let mut cfg: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&pj)?)?;
-    cfg["parent_folder"] = serde_json::Value::String(format!("../../../{}", parent.clone()));
+    let pj = PathBuf::from("project.json");
+    let mut cfg: serde_json::Value = match std::fs::read_to_string(&pj) {
+        Ok(content) => serde_json::from_str(&content)?,
+        Err(_) => serde_json::json!({}),
+    };
+    cfg["parent_folder"] = serde_json::Value::String(parent.clone());
     std::fs::write(&pj, serde_json::to_string_pretty(&cfg)?)?;
 
     println!("Updated project.json parent_folder to {}", parent);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Print the route manifest of the project as a table, or as JSON with `--json`. The project
+/// is built if needed and prints the manifest compiled into it (`cargo run -- --routes`).
+fn cmd_routes() -> Result<()> {
+    let output = std::process::Command::new("cargo")
+        .args(["run", "--quiet", "--", "--routes"])
+        .stderr(std::process::Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("`cargo run -- --routes` exited with {}", output.status);
+    }
+    let content = String::from_utf8(output.stdout)?;
+    if std::env::args().skip(2).any(|arg| arg == "--json") {
+        print!("{}", content);
+        return Ok(());
+    }
+    let manifest: serde_json::Value = serde_json::from_str(&content)?;
+    let routes = manifest["routes"].as_array().cloned().unwrap_or_default();
+
+    let mut rows = vec![["METHODS".to_string(), "PATTERN".to_string(), "TYPE".to_string(), "FILE".to_string()]];
+    for route in &routes {
+        let methods: Vec<&str> = route["methods"].as_array()
+            .map(|m| m.iter().filter_map(|m| m.as_str()).collect())
+            .unwrap_or_default();
+        rows.push([
+            methods.join(","),
+            route["pattern"].as_str().unwrap_or_default().to_string(),
+            route["type"].as_str().unwrap_or_default().to_string(),
+            route["file"].as_str().unwrap_or_default().to_string(),
+        ]);
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in &rows {
+        println!("{:<w0$}  {:<w1$}  {:<w2$}  {}", row[0], row[1], row[2], row[3], w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+    }
+    println!("\n{} route(s)", routes.len());
+    Ok(())
+}
@@ -0,0 +1,150 @@
+// The `/_dev/routes` explorer page, served in dev mode
+use crate::engine::route_table::RouteTable;
+use crate::engine::router::Router;
+use rsf_pattern::{route_pattern, ParamSpec, PatternSeg};
+
+/// Where the route explorer is served in dev mode
+pub const DEV_ROUTES_PATH: &str = "/_dev/routes";
+
+/// A row of the explorer: one route pattern and where it comes from
+struct Entry<'a> {
+    pattern: &'a str,
+    methods: Vec<&'static str>,
+    source: String,
+}
+
+/// Every route of the app, route files and `Router` routes alike, with its methods, params and
+/// source, above a form that sends requests to them
+pub(crate) fn routes_page(routes: &RouteTable, router: &Router) -> String {
+    let mut entries: Vec<Entry> = Vec::new();
+    for info in routes.routes {
+        match entries.iter_mut().find(|e| e.pattern == info.pattern) {
+            Some(entry) => entry.methods.push(info.method),
+            None => entries.push(Entry { pattern: info.pattern, methods: vec![info.method], source: info.file.to_string() }),
+        }
+    }
+    for route in router.routes() {
+        entries.push(Entry { pattern: &route.pattern, methods: route.methods.methods().collect(), source: format!("Router: {}", route.path) });
+    }
+    entries.sort_by(|a, b| a.pattern.cmp(b.pattern));
+
+    let mut rows = String::new();
+    for entry in &entries {
+        let methods: Vec<String> = entry.methods.iter().map(|m| format!("<button type=\"button\" data-method=\"{m}\">{m}</button>")).collect();
+        rows.push_str(&format!(
+            "<tr data-path=\"{path}\"><td><code>{path}</code></td><td>{methods}</td><td>{params}</td><td><code>{source}</code></td></tr>\n",
+            path = escape(entry.pattern),
+            methods = methods.join(" "),
+            params = escape(&params(entry.pattern).join(", ")),
+            source = escape(&entry.source),
+        ));
+    }
+    ROUTES_PAGE
+        .replace("{count}", &entries.len().to_string())
+        .replace("{rows}", &rows)
+}
+
+/// Params of a pattern as written in it: `id`, `id: u64`, `...slug`, `[...slug]`
+fn params(pattern: &str) -> Vec<String> {
+    route_pattern(pattern)
+        .into_iter()
+        .filter_map(|seg| match seg {
+            PatternSeg::Static(_) => None,
+            PatternSeg::Param(name, None) => Some(name.to_string()),
+            PatternSeg::Param(name, Some(ParamSpec::Type(ty))) => Some(format!("{}: {}", name, ty)),
+            PatternSeg::Param(name, Some(ParamSpec::Regex(re))) => Some(format!("{}: regex({})", name, re)),
+            PatternSeg::CatchAll(name) => Some(format!("...{}", name)),
+            PatternSeg::OptionalCatchAll(name) => Some(format!("[...{}]", name)),
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Clicking a method fills the form with the route; params stay as `[name]` to be replaced
+const ROUTES_PAGE: &str = r##"<!doctype html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>Routes</title>
+  <style>
+    body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+    table { border-collapse: collapse; width: 100%; }
+    th, td { text-align: left; padding: .4rem .6rem; border-bottom: 1px solid #ddd; vertical-align: top; }
+    button { font: inherit; font-size: .8rem; cursor: pointer; }
+    form { display: grid; gap: .5rem; max-width: 48rem; margin-top: 2rem; }
+    input, select, textarea { font-family: monospace; padding: .3rem; }
+    pre { background: #f5f5f5; padding: 1rem; overflow: auto; }
+  </style>
+</head>
+<body>
+  <h1>Routes ({count})</h1>
+  <table>
+    <thead><tr><th>Pattern</th><th>Methods</th><th>Params</th><th>Source</th></tr></thead>
+    <tbody>
+{rows}    </tbody>
+  </table>
+
+  <h2>Try a request</h2>
+  <form id="try">
+    <div>
+      <select name="method">
+        <option>GET</option><option>POST</option><option>PUT</option><option>DELETE</option>
+        <option>PATCH</option><option>OPTIONS</option><option>HEAD</option>
+      </select>
+      <input name="path" size="50" value="/" required>
+    </div>
+    <textarea name="headers" rows="2" placeholder="Content-Type: application/json"></textarea>
+    <textarea name="body" rows="5" placeholder="Request body"></textarea>
+    <button type="submit">Send</button>
+  </form>
+  <pre id="response"></pre>
+
+  <script>
+    const form = document.getElementById("try");
+    const output = document.getElementById("response");
+    document.querySelectorAll("button[data-method]").forEach(button => {
+      button.addEventListener("click", () => {
+        form.method.value = button.dataset.method;
+        form.path.value = button.closest("tr").dataset.path;
+        form.path.focus();
+      });
+    });
+    form.addEventListener("submit", async event => {
+      event.preventDefault();
+      const headers = {};
+      for (const line of form.headers.value.split("\n")) {
+        const i = line.indexOf(":");
+        if (i > 0) headers[line.slice(0, i).trim()] = line.slice(i + 1).trim();
+      }
+      const method = form.method.value;
+      const body = ["GET", "HEAD"].includes(method) || !form.body.value ? undefined : form.body.value;
+      output.textContent = "...";
+      try {
+        const res = await fetch(form.path.value, { method, headers, body });
+        const lines = [`${res.status} ${res.statusText}`];
+        res.headers.forEach((value, name) => lines.push(`${name}: ${value}`));
+        output.textContent = lines.join("\n") + "\n\n" + await res.text();
+      } catch (err) {
+        output.textContent = String(err);
+      }
+    });
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_params() {
+        assert_eq!(params("/posts/[id]/comments/[commentId:u64]"), ["id", "commentId: u64"]);
+        assert_eq!(params("/docs/[[...slug]]"), ["[...slug]"]);
+        assert!(params("/").is_empty());
+        assert_eq!(escape(r#"<a href="x">&</a>"#), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+}
@@ -1,6 +1,5 @@
 // Better error handling for the framework
 use thiserror::Error;
-use std::fmt;
 
 #[derive(Error, Debug)]
 pub enum FrameworkError {
@@ -12,6 +11,24 @@ pub enum FrameworkError {
     
     #[error("Parse error: {0}")]
     ParseError(String),
+
+    #[error("Payload too large: the limit is {limit} bytes")]
+    PayloadTooLarge { limit: usize },
+
+    #[error("Unauthorized: valid {scheme} credentials required")]
+    Unauthorized { scheme: &'static str },
+
+    #[error("Too many requests: retry after {retry_after} seconds")]
+    TooManyRequests { retry_after: u64 },
+
+    #[error("Handler timed out: no response within {ms} ms")]
+    Timeout { ms: u128 },
+
+    #[error("Unsupported media type: expected {expected}")]
+    UnsupportedMediaType { expected: &'static str },
+
+    #[error("Unprocessable entity: {0}")]
+    UnprocessableEntity(String),
     
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
@@ -26,6 +43,12 @@ impl FrameworkError {
             Self::RouteNotFound { .. } => 404,
             Self::InvalidMethod { .. } => 405,
             Self::ParseError(_) => 400,
+            Self::PayloadTooLarge { .. } => 413,
+            Self::Unauthorized { .. } => 401,
+            Self::TooManyRequests { .. } => 429,
+            Self::Timeout { .. } => 503,
+            Self::UnsupportedMediaType { .. } => 415,
+            Self::UnprocessableEntity(_) => 422,
             Self::IoError(_) => 500,
             Self::InternalError(_) => 500,
         }
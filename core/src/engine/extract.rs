@@ -0,0 +1,244 @@
+// Typed request extractors: handler arguments built from the request
+use crate::engine::devx::RequestContext;
+use crate::engine::errors::FrameworkError;
+use crate::engine::request::{BodyParser, Request};
+use crate::engine::response::Json;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Unexpected, Visitor};
+use serde::de::value::{Error as ValueError, MapDeserializer};
+use std::collections::HashMap;
+
+/// A value a route handler can take as an argument. The generated wrappers call
+/// `from_request` for every argument that isn't a `&Request`, the params map or a path param
+/// taken by name, and a failure is rendered like any other error (through the route's
+/// `_error.rs`, if it has one):
+///
+/// ```ignore
+/// pub fn POST(Path(id): Path<u64>, Json(body): Json<CreatePost>) -> (Json<Post>, u16) { .. }
+/// pub fn GET(Query(page): Query<Page>, Header(user_agent): Header<String>) -> String { .. }
+/// ```
+///
+/// `name` is the name the argument is bound to (`user_agent` above, empty for `_`): `Header`,
+/// `Cookie` and `Path` look their value up by it. The body is already buffered, so body
+/// extractors can appear anywhere in the list and more than one can read it.
+pub trait FromRequest: Sized {
+    fn from_request(req: &Request, name: &str) -> Result<Self, FrameworkError>;
+}
+
+/// Path params: the one named like the binding (`Path(id): Path<u64>`), or all of them
+/// deserialized into a struct or map (`Path(p): Path<PostPath>`). Invalid values are a 400.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Path<T>(pub T);
+
+/// The query string deserialized into `T`; a 400 when it doesn't fit
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+/// An `application/x-www-form-urlencoded` body deserialized into `T`: a 415 for another
+/// content type, a 422 when the fields don't fit `T`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Form<T>(pub T);
+
+/// The header named like the binding, with `_` read as `-` (`Header(user_agent)` reads
+/// `User-Agent`); a 400 when it is missing or doesn't parse
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Header<T>(pub T);
+
+/// The cookie named like the binding; a 400 when it is missing or doesn't parse
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cookie<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest for Path<T> {
+    fn from_request(req: &Request, name: &str) -> Result<Self, FrameworkError> {
+        let value = match req.params.get(name) {
+            Some(value) => T::deserialize(Text(value)),
+            None => from_map(&req.params),
+        };
+        value.map(Path).map_err(|e| FrameworkError::ParseError(format!("invalid path parameters: {}", e)))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    fn from_request(req: &Request, _name: &str) -> Result<Self, FrameworkError> {
+        from_map(&req.query).map(Query).map_err(|e| FrameworkError::ParseError(format!("invalid query string: {}", e)))
+    }
+}
+
+/// An `application/json` (or `+json`) body: a 415 for another content type, a 400 for
+/// malformed JSON and a 422 when it doesn't fit `T`
+impl<T: DeserializeOwned> FromRequest for Json<T> {
+    fn from_request(req: &Request, _name: &str) -> Result<Self, FrameworkError> {
+        let media_type = media_type(req);
+        if media_type != "application/json" && !media_type.ends_with("+json") {
+            return Err(FrameworkError::UnsupportedMediaType { expected: "application/json" });
+        }
+        serde_json::from_slice(&req.body).map(Json).map_err(|e| match e.classify() {
+            serde_json::error::Category::Data => FrameworkError::UnprocessableEntity(e.to_string()),
+            _ => FrameworkError::ParseError(format!("invalid JSON body: {}", e)),
+        })
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Form<T> {
+    fn from_request(req: &Request, _name: &str) -> Result<Self, FrameworkError> {
+        if media_type(req) != "application/x-www-form-urlencoded" {
+            return Err(FrameworkError::UnsupportedMediaType { expected: "application/x-www-form-urlencoded" });
+        }
+        let fields = BodyParser::form(&req.body).map_err(FrameworkError::ParseError)?;
+        from_map(&fields).map(Form).map_err(|e| FrameworkError::UnprocessableEntity(e.to_string()))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Header<T> {
+    fn from_request(req: &Request, name: &str) -> Result<Self, FrameworkError> {
+        let header = name.replace('_', "-");
+        let Some(value) = req.header(&header) else {
+            return Err(FrameworkError::ParseError(format!("missing header `{}`", header)));
+        };
+        T::deserialize(Text(value)).map(Header).map_err(|e| FrameworkError::ParseError(format!("invalid header `{}`: {}", header, e)))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Cookie<T> {
+    fn from_request(req: &Request, name: &str) -> Result<Self, FrameworkError> {
+        let value = req.header("cookie").and_then(|cookies| {
+            cookies.split(';').find_map(|pair| match pair.trim().split_once('=') {
+                Some((key, value)) if key == name => Some(value.trim_matches('"')),
+                _ => None,
+            })
+        });
+        let Some(value) = value else {
+            return Err(FrameworkError::ParseError(format!("missing cookie `{}`", name)));
+        };
+        T::deserialize(Text(value)).map(Cookie).map_err(|e| FrameworkError::ParseError(format!("invalid cookie `{}`: {}", name, e)))
+    }
+}
+
+/// `None` instead of an error, e.g. `Header(if_none_match): Option<Header<String>>`
+impl<T: FromRequest> FromRequest for Option<T> {
+    fn from_request(req: &Request, name: &str) -> Result<Self, FrameworkError> {
+        Ok(T::from_request(req, name).ok())
+    }
+}
+
+impl FromRequest for RequestContext {
+    fn from_request(req: &Request, _name: &str) -> Result<Self, FrameworkError> {
+        Ok(RequestContext {
+            method: req.method.clone(),
+            path: req.path.clone(),
+            params: req.params.clone(),
+            query: req.query.clone(),
+            headers: req.headers.clone(),
+        })
+    }
+}
+
+/// The `Content-Type` without its parameters, lower-cased
+fn media_type(req: &Request) -> String {
+    req.header("content-type")
+        .map(|value| value.split(';').next().unwrap_or("").trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+fn from_map<T: DeserializeOwned>(map: &HashMap<String, String>) -> Result<T, ValueError> {
+    T::deserialize(MapDeserializer::new(map.iter().map(|(k, v)| (k.as_str(), Text(v)))))
+}
+
+/// Deserializes a text value (path param, query value, header, cookie) into whatever type
+/// asks for it: numbers and booleans are parsed, strings are taken as they are.
+struct Text<'a>(&'a str);
+
+macro_rules! parse_text {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+            match self.0.parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for Text<'_> {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_str(self.0)
+    }
+
+    parse_text! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants by name, e.g. `?sort=newest` into `enum Sort { Newest, Oldest }`
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_enum(IntoDeserializer::<ValueError>::into_deserializer(self.0))
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ValueError> for Text<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_extractors() {
+        let req = Request::new("POST".to_string(), "/posts/7?page=2&tag=rust".to_string())
+            .with_params(HashMap::from([("id".to_string(), "7".to_string())]))
+            .with_header("Content-Type".to_string(), "application/json; charset=utf-8".to_string())
+            .with_header("User-Agent".to_string(), "curl".to_string())
+            .with_header("Cookie".to_string(), "theme=dark; session=abc".to_string())
+            .with_body(Bytes::from_static(br#"{"title": "Hi"}"#));
+
+        let Path(id) = Path::<u64>::from_request(&req, "id").unwrap();
+        assert_eq!(id, 7);
+        let Query(query) = Query::<HashMap<String, String>>::from_request(&req, "").unwrap();
+        assert_eq!(query.get("tag").map(String::as_str), Some("rust"));
+        let Header(user_agent) = Header::<String>::from_request(&req, "user_agent").unwrap();
+        assert_eq!(user_agent, "curl");
+        let Cookie(session) = Cookie::<String>::from_request(&req, "session").unwrap();
+        assert_eq!(session, "abc");
+        let Json(body) = Json::<HashMap<String, String>>::from_request(&req, "").unwrap();
+        assert_eq!(body["title"], "Hi");
+
+        let status = |err: FrameworkError| err.status_code();
+        assert_eq!(Json::<HashMap<String, u32>>::from_request(&req, "").map_err(status).err(), Some(422));
+        assert_eq!(Query::<(u8, u8)>::from_request(&req, "").map_err(status).err(), Some(400));
+        assert_eq!(Header::<u32>::from_request(&req, "user_agent").map_err(status).err(), Some(400));
+        assert!(Option::<Cookie<String>>::from_request(&req, "missing").unwrap().is_none());
+        let form = Request::new("POST".to_string(), "/posts".to_string()).with_body(req.body.clone());
+        assert_eq!(Json::<HashMap<String, String>>::from_request(&form, "").map_err(status).err(), Some(415));
+    }
+}
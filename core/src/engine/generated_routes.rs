@@ -1,1122 +0,0 @@
-// GENERATED FILE - DO NOT EDIT
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_main_rs {
-    mod __orig {
-        //'api'
-        // import hashmap
-        use std::collections::HashMap;
-        
-        pub(crate) fn GET(_params: HashMap<String, String>) -> (String, u16) {
-            ("Hello from GET".to_string(), 500)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params.clone());
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_api_rs {
-    mod __orig {
-        //'api'
-        // Example API route showcasing new DX features
-        use std::collections::HashMap;
-        
-        pub(crate) fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // Simple JSON response example
-            let response = format!(
-                r#"{{"message": "Hello from the API!", "params": {}}}"#,
-                serde_json::to_string(params).unwrap_or_else(|_| "{}".to_string())
-            );
-            (response, 200)
-        }
-        
-        pub(crate) fn POST(_params: &HashMap<String, String>) -> (String, u16) {
-            let response = r#"{"message": "POST request received", "status": "success"}"#;
-            (response.to_string(), 201)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for POST that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn POST(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::POST(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_users__id__rs {
-    mod __orig {
-        //'api'
-        // Dynamic route example: /users/:id
-        use std::collections::HashMap;
-        
-        pub(crate) fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            if let Some(id) = params.get("id") {
-                let response = format!(
-                    r#"{{"user_id": "{}", "name": "User {}", "status": "active"}}"#,
-                    id, id
-                );
-                (response, 200)
-            } else {
-                (r#"{"error": "User ID required"}"#.to_string(), 400)
-            }
-        }
-        
-        pub(crate) fn DELETE(params: &HashMap<String, String>) -> (String, u16) {
-            if let Some(id) = params.get("id") {
-                let response = format!(r#"{{"deleted": true, "user_id": "{}"}}"#, id);
-                (response, 200)
-            } else {
-                (r#"{"error": "User ID required"}"#.to_string(), 400)
-            }
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for DELETE that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn DELETE(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::DELETE(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_posts__id__comments__commentId__rs {
-    mod __orig {
-        //'api'
-        // Dynamic route with multiple params: /posts/:id/comments/:commentId
-        use std::collections::HashMap;
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            let post_id = params.get("id").map(|s| s.as_str()).unwrap_or("unknown");
-            let comment_id = params.get("commentId").map(|s| s.as_str()).unwrap_or("unknown");
-            
-            let response = format!(
-                r#"{{"post_id": "{}", "comment_id": "{}", "content": "This is comment {} on post {}"}}"#,
-                post_id, comment_id, comment_id, post_id
-            );
-            (response, 200)
-        }
-        
-        pub fn DELETE(params: &HashMap<String, String>) -> (String, u16) {
-            let post_id = params.get("id").map(|s| s.as_str()).unwrap_or("unknown");
-            let comment_id = params.get("commentId").map(|s| s.as_str()).unwrap_or("unknown");
-            
-            let response = format!(
-                r#"{{"deleted": true, "post_id": "{}", "comment_id": "{}"}}"#,
-                post_id, comment_id
-            );
-            (response, 200)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for DELETE that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn DELETE(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::DELETE(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_search_rs {
-    mod __orig {
-        //'api'
-        // Example demonstrating query string parsing
-        use std::collections::HashMap;
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // In a real implementation, you'd parse from the actual request
-            // For now, demonstrate the concept
-            let response = r#"{"message": "Search endpoint", "tip": "Use ?q=search&limit=10"}"#;
-            (response.to_string(), 200)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_api_admin_users_rs {
-    mod __orig {
-        // ✅ REAL EXAMPLE: Protected Admin Route with JWT
-        use std::collections::HashMap;
-        use serde::Serialize;
-        use core::engine::{JwtAuth, FrameworkError};
-        use once_cell::sync::Lazy;
-        
-        // Global JWT instance (same secret as login route)
-        static JWT: Lazy<JwtAuth> = Lazy::new(|| {
-            JwtAuth::new("your-secret-key-change-in-production".to_string())
-        });
-        
-        #[derive(Serialize)]
-        struct User {
-            id: String,
-            username: String,
-            email: String,
-            role: String,
-            created_at: String,
-        }
-        
-        #[derive(Serialize)]
-        struct ErrorResponse {
-            error: String,
-        }
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: JWT Authentication Check
-            // In production, extract from Authorization: Bearer <token> header
-            let token = params.get("auth_token")
-                .or_else(|| params.get("token"))
-                .map(|s| s.as_str())
-                .unwrap_or("");
-            
-            // Verify JWT token
-            let user_id = match JWT.verify_token(token) {
-                Ok(id) => id,
-                Err(e) => {
-                    let error = ErrorResponse { 
-                        error: format!("Unauthorized: {}", e) 
-                    };
-                    return (serde_json::to_string(&error).unwrap(), 401);
-                }
-            };
-            
-            // ✅ Authorization: Check if user is admin
-            // In production, check role from database
-            if !user_id.contains("admin") {
-                let error = ErrorResponse {
-                    error: "Forbidden: Admin access required".to_string()
-                };
-                return (serde_json::to_string(&error).unwrap(), 403);
-            }
-            
-            println!("✅ Admin access granted for user: {}", user_id);
-            
-            // Fetch users from database (mocked)
-            let users = vec![
-                User {
-                    id: "usr_001".to_string(),
-                    username: "admin".to_string(),
-                    email: "admin@example.com".to_string(),
-                    role: "admin".to_string(),
-                    created_at: "2025-01-01T00:00:00Z".to_string(),
-                },
-                User {
-                    id: "usr_002".to_string(),
-                    username: "john".to_string(),
-                    email: "john@example.com".to_string(),
-                    role: "user".to_string(),
-                    created_at: "2025-02-15T10:30:00Z".to_string(),
-                },
-                User {
-                    id: "usr_003".to_string(),
-                    username: "jane".to_string(),
-                    email: "jane@example.com".to_string(),
-                    role: "user".to_string(),
-                    created_at: "2025-03-20T14:45:00Z".to_string(),
-                },
-            ];
-            
-            match serde_json::to_string(&users) {
-                Ok(json) => (json, 200),
-                Err(_) => {
-                    let error = ErrorResponse { error: "Internal server error".to_string() };
-                    (serde_json::to_string(&error).unwrap(), 500)
-                }
-            }
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_api_upload_rs {
-    mod __orig {
-        // ✅ REAL EXAMPLE: File Upload with Validation
-        use std::collections::HashMap;
-        use serde::Serialize;
-        use core::engine::BodyParser;
-        
-        #[derive(Serialize)]
-        struct UploadResponse {
-            success: bool,
-            url: String,
-            size: usize,
-            filename: String,
-            content_type: String,
-        }
-        
-        #[derive(Serialize)]
-        struct ErrorResponse {
-            error: String,
-        }
-        
-        pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: Binary Body Parsing
-            // Mock binary data (in production, this comes from multipart/form-data)
-            let mock_file_data = b"Mock PDF file content...".repeat(100);
-            let filename = "document.pdf";
-            
-            // Parse binary body
-            let file_bytes = match BodyParser::bytes(mock_file_data.as_slice()) {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    let error = ErrorResponse { error: format!("Failed to read file: {}", e) };
-                    return (serde_json::to_string(&error).unwrap(), 400);
-                }
-            };
-            
-            let file_size = file_bytes.len();
-            
-            // ✅ Validation: File size limit (10MB)
-            const MAX_SIZE: usize = 10 * 1024 * 1024;
-            if file_size > MAX_SIZE {
-                let error = ErrorResponse { 
-                    error: format!("File too large: {} bytes (max: {} bytes)", file_size, MAX_SIZE) 
-                };
-                return (serde_json::to_string(&error).unwrap(), 413);
-            }
-            
-            // ✅ Validation: File type (basic check by extension)
-            let allowed_extensions = ["pdf", "doc", "docx", "txt", "jpg", "png"];
-            let extension = filename.split('.').last().unwrap_or("");
-            
-            if !allowed_extensions.contains(&extension) {
-                let error = ErrorResponse {
-                    error: format!("Invalid file type: .{} (allowed: {})", 
-                        extension, 
-                        allowed_extensions.join(", "))
-                };
-                return (serde_json::to_string(&error).unwrap(), 415);
-            }
-            
-            // Generate unique filename
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis();
-            
-            let unique_filename = format!("{}_{}.{}", 
-                timestamp,
-                filename.split('.').next().unwrap_or("file"),
-                extension
-            );
-            
-            let file_path = format!("/uploads/2025/10/{}", unique_filename);
-            
-            // In production: Save to disk or cloud storage
-            println!("✅ File upload processed:");
-            println!("   Original: {}", filename);
-            println!("   Saved as: {}", unique_filename);
-            println!("   Size: {} bytes ({:.2} KB)", file_size, file_size as f64 / 1024.0);
-            println!("   Type: {}", extension);
-            
-            let response = UploadResponse {
-                success: true,
-                url: file_path,
-                size: file_size,
-                filename: unique_filename,
-                content_type: match extension {
-                    "pdf" => "application/pdf",
-                    "jpg" | "jpeg" => "image/jpeg",
-                    "png" => "image/png",
-                    "txt" => "text/plain",
-                    _ => "application/octet-stream",
-                }.to_string(),
-            };
-            
-            (serde_json::to_string(&response).unwrap(), 201)
-        }
-    }
-    // wrapper for POST that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn POST(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::POST(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_api_public_data_rs {
-    mod __orig {
-        // ✅ REAL EXAMPLE: CORS-Enabled Public API
-        use std::collections::HashMap;
-        use serde::Serialize;
-        use core::engine::CorsMiddleware;
-        
-        #[derive(Serialize)]
-        struct PublicData {
-            items: Vec<DataItem>,
-            total: usize,
-            timestamp: u64,
-        }
-        
-        #[derive(Serialize)]
-        struct DataItem {
-            id: String,
-            name: String,
-            value: f64,
-        }
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: CORS Configuration
-            let _cors = CorsMiddleware::new()
-                .allow_origin("*")
-                .allow_methods(vec!["GET".to_string(), "OPTIONS".to_string()]);
-            
-            // Note: In production, CORS headers would be added to response:
-            // for (key, value) in cors.headers() {
-            //     response.headers.push((key, value));
-            // }
-            
-            let data = PublicData {
-                items: vec![
-                    DataItem {
-                        id: "item_1".to_string(),
-                        name: "CPU Usage".to_string(),
-                        value: 42.5,
-                    },
-                    DataItem {
-                        id: "item_2".to_string(),
-                        name: "Memory Usage".to_string(),
-                        value: 68.3,
-                    },
-                    DataItem {
-                        id: "item_3".to_string(),
-                        name: "Disk I/O".to_string(),
-                        value: 15.7,
-                    },
-                ],
-                total: 3,
-                timestamp: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs(),
-            };
-            
-            // Demonstrate CORS headers that would be added
-            println!("✅ CORS headers configured:");
-            println!("   Access-Control-Allow-Origin: *");
-            println!("   Access-Control-Allow-Methods: GET, OPTIONS");
-            
-            match serde_json::to_string(&data) {
-                Ok(json) => (json, 200),
-                Err(_) => (r#"{"error": "Failed to serialize data"}"#.to_string(), 500),
-            }
-        }
-        
-        pub fn OPTIONS(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: CORS Preflight Response
-            let _cors = CorsMiddleware::new()
-                .allow_origin("*")
-                .allow_methods(vec!["GET".to_string(), "OPTIONS".to_string()]);
-            
-            // CORS headers would be added here
-            println!("✅ CORS preflight handled");
-            
-            ("".to_string(), 204)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for OPTIONS that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn OPTIONS(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::OPTIONS(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_api_large-data_rs {
-    mod __orig {
-        // ✅ REAL EXAMPLE: Response Compression
-        use std::collections::HashMap;
-        use serde::Serialize;
-        use core::engine::ResponseCompressor;
-        
-        #[derive(Serialize)]
-        struct DataPoint {
-            id: usize,
-            value: f64,
-            label: String,
-            metadata: String,
-        }
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // Generate large dataset (perfect for compression)
-            let mut data_points = Vec::with_capacity(1000);
-            for i in 0..1000 {
-                data_points.push(DataPoint {
-                    id: i,
-                    value: (i as f64) * 1.5 + 10.0,
-                    label: format!("Data point #{}", i),
-                    metadata: format!("Additional metadata for point {} with some extra text to increase size", i),
-                });
-            }
-            
-            let json = serde_json::to_string(&data_points).unwrap();
-            let original_size = json.len();
-            
-            // ✅ REAL FEATURE: Gzip Compression
-            // Only compress if > 1KB and beneficial
-            if let Some(compressed) = ResponseCompressor::compress_gzip(json.as_bytes(), 1024) {
-                let compressed_size = compressed.len();
-                let ratio = (100.0 * compressed_size as f64) / original_size as f64;
-                
-                println!("✅ Response compressed!");
-                println!("   Original: {} bytes", original_size);
-                println!("   Compressed: {} bytes", compressed_size);
-                println!("   Compression ratio: {:.1}%", ratio);
-                println!("   Savings: {} bytes ({:.1}%)", 
-                    original_size - compressed_size,
-                    100.0 - ratio);
-                
-                // In production, would return compressed with header:
-                // Content-Encoding: gzip
-                // For demo, return original with stats
-                let stats = serde_json::json!({
-                    "compression": {
-                        "enabled": true,
-                        "original_size": original_size,
-                        "compressed_size": compressed_size,
-                        "ratio": format!("{:.1}%", ratio),
-                        "savings_bytes": original_size - compressed_size
-                    },
-                    "data_points": 1000,
-                    "note": "In production, this would be sent as gzip with Content-Encoding header"
-                });
-                
-                return (stats.to_string(), 200);
-            }
-            
-            // Response too small for compression
-            println!("✅ Compression skipped (response < 1KB or not beneficial)");
-            println!("   Size: {} bytes", original_size);
-            
-            (json, 200)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_auth_login_rs {
-    mod __orig {
-        //'api'
-        // ✅ REAL EXAMPLE: Authentication with JWT and Sessions
-        use std::collections::HashMap;
-        use serde::{Deserialize, Serialize};
-        use core::engine::{JwtAuth, SessionStore, BodyParser};
-        use std::time::Duration;
-        use once_cell::sync::Lazy;
-        
-        // Global instances (in production, use dependency injection)
-        static JWT: Lazy<JwtAuth> = Lazy::new(|| {
-            JwtAuth::new("your-secret-key-change-in-production".to_string())
-        });
-        
-        static SESSIONS: Lazy<SessionStore> = Lazy::new(|| {
-            SessionStore::new(Duration::from_secs(3600)) // 1 hour TTL
-        });
-        
-        #[derive(Deserialize)]
-        struct LoginRequest {
-            username: String,
-            password: String,
-        }
-        
-        #[derive(Serialize)]
-        struct LoginResponse {
-            success: bool,
-            token: String,
-            user_id: String,
-            session_id: String,
-            expires_in: u64,
-        }
-        
-        #[derive(Serialize)]
-        struct ErrorResponse {
-            error: String,
-        }
-        
-        pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: JSON Body Parsing
-            let mock_body = r#"{"username": "admin", "password": "admin123"}"#;
-            
-            let credentials = match BodyParser::json::<LoginRequest>(mock_body.as_bytes()) {
-                Ok(creds) => creds,
-                Err(e) => {
-                    let error = ErrorResponse { error: format!("Invalid JSON: {}", e) };
-                    return (serde_json::to_string(&error).unwrap(), 400);
-                }
-            };
-            
-            // Simple credential check (use database in production)
-            if credentials.username != "admin" || credentials.password != "admin123" {
-                let error = ErrorResponse { error: "Invalid credentials".to_string() };
-                return (serde_json::to_string(&error).unwrap(), 401);
-            }
-            
-            let user_id = format!("user_{}", credentials.username);
-            
-            // ✅ REAL FEATURE: JWT Token Generation
-            let token = JWT.generate_token(&user_id, Duration::from_secs(86400)); // 24 hours
-            
-            // ✅ REAL FEATURE: Session Creation
-            let session_id = format!("sess_{}", std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis());
-            
-            let mut session = SESSIONS.create(session_id.clone());
-            session.set("user_id".to_string(), user_id.clone());
-            session.set("username".to_string(), credentials.username);
-            SESSIONS.update(session);
-            
-            let response = LoginResponse {
-                success: true,
-                token,
-                user_id,
-                session_id,
-                expires_in: 3600,
-            };
-            
-            (serde_json::to_string(&response).unwrap(), 200)
-        }
-        
-        // ✅ REAL FEATURE: Token Verification
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            // Verify JWT token (extract from Authorization header in production)
-            let token = params.get("token").map(|s| s.as_str()).unwrap_or("");
-            
-            match JWT.verify_token(token) {
-                Ok(user_id) => {
-                    let response = serde_json::json!({
-                        "valid": true,
-                        "user_id": user_id,
-                        "message": "Token is valid"
-                    });
-                    (response.to_string(), 200)
-                }
-                Err(e) => {
-                    let error = ErrorResponse { error: format!("Invalid token: {}", e) };
-                    (serde_json::to_string(&error).unwrap(), 401)
-                }
-            }
-        }
-        
-        // ✅ REAL FEATURE: Session Logout
-        pub fn DELETE(params: &HashMap<String, String>) -> (String, u16) {
-            let session_id = params.get("session_id")
-                .or_else(|| params.get("id"))
-                .map(|s| s.as_str())
-                .unwrap_or("");
-            
-            SESSIONS.delete(session_id);
-            
-            let response = serde_json::json!({
-                "success": true,
-                "message": "Logged out successfully"
-            });
-            
-            (response.to_string(), 200)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for POST that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn POST(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::POST(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for DELETE that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn DELETE(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::DELETE(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_posts_rs {
-    mod __orig {
-        //'api'
-        // ✅ REAL EXAMPLE: Request Body Parsing with Validation
-        use std::collections::HashMap;
-        use serde::{Deserialize, Serialize};
-        use core::engine::BodyParser;
-        
-        #[derive(Deserialize, Serialize)]
-        struct CreatePostRequest {
-            title: String,
-            content: String,
-            tags: Vec<String>,
-        }
-        
-        #[derive(Serialize)]
-        struct CreatePostResponse {
-            id: String,
-            title: String,
-            content: String,
-            tags: Vec<String>,
-            created_at: String,
-        }
-        
-        #[derive(Serialize)]
-        struct PostListResponse {
-            posts: Vec<PostSummary>,
-            total: usize,
-            page: u32,
-            per_page: u32,
-        }
-        
-        #[derive(Serialize)]
-        struct PostSummary {
-            id: String,
-            title: String,
-            excerpt: String,
-            tags: Vec<String>,
-        }
-        
-        pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
-            // ✅ REAL FEATURE: JSON Body Parsing
-            let mock_body = r#"{
-                "title": "My First Post",
-                "content": "This is the content of my first blog post!",
-                "tags": ["rust", "web", "performance"]
-            }"#;
-            
-            let post_data = match BodyParser::json::<CreatePostRequest>(mock_body.as_bytes()) {
-                Ok(data) => data,
-                Err(e) => {
-                    return (format!(r#"{{"error": "Invalid JSON: {}"}}"#, e), 400);
-                }
-            };
-            
-            // ✅ Validation
-            if post_data.title.trim().is_empty() {
-                return (r#"{"error": "Title is required"}"#.to_string(), 400);
-            }
-            
-            if post_data.title.len() > 200 {
-                return (r#"{"error": "Title too long (max 200 chars)"}"#.to_string(), 400);
-            }
-            
-            if post_data.content.trim().is_empty() {
-                return (r#"{"error": "Content is required"}"#.to_string(), 400);
-            }
-            
-            // Generate ID and timestamp
-            let post_id = format!("post_{}", std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis());
-            
-            let created_at = chrono::Utc::now().to_rfc3339();
-            
-            // Create response
-            let response = CreatePostResponse {
-                id: post_id,
-                title: post_data.title,
-                content: post_data.content,
-                tags: post_data.tags,
-                created_at,
-            };
-            
-            (serde_json::to_string(&response).unwrap(), 201)
-        }
-        
-        // ✅ REAL FEATURE: Query String Parsing with Pagination
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            use core::engine::QueryParser;
-            
-            // Mock query string (in production, extract from request URL)
-            let query_string = "page=2&per_page=5&tag=rust";
-            let query_params = QueryParser::parse(query_string);
-            
-            // Extract pagination params with defaults
-            let page: u32 = query_params.get("page")
-                .and_then(|p| p.parse().ok())
-                .unwrap_or(1);
-            
-            let per_page: u32 = query_params.get("per_page")
-                .and_then(|p| p.parse().ok())
-                .unwrap_or(10);
-            
-            let tag_filter = query_params.get("tag");
-            
-            // Mock database query
-            let mut posts = vec![
-                PostSummary {
-                    id: "post_1".to_string(),
-                    title: "Getting Started with Rust".to_string(),
-                    excerpt: "Learn the basics of Rust programming...".to_string(),
-                    tags: vec!["rust".to_string(), "tutorial".to_string()],
-                },
-                PostSummary {
-                    id: "post_2".to_string(),
-                    title: "Web Performance Optimization".to_string(),
-                    excerpt: "Tips for building fast web applications...".to_string(),
-                    tags: vec!["web".to_string(), "performance".to_string()],
-                },
-                PostSummary {
-                    id: "post_3".to_string(),
-                    title: "Advanced Rust Patterns".to_string(),
-                    excerpt: "Explore advanced Rust programming patterns...".to_string(),
-                    tags: vec!["rust".to_string(), "advanced".to_string()],
-                },
-            ];
-            
-            // Filter by tag if provided
-            if let Some(tag) = tag_filter {
-                posts.retain(|p| p.tags.contains(tag));
-            }
-            
-            let total = posts.len();
-            
-            // Apply pagination
-            let start = ((page - 1) * per_page) as usize;
-            let paginated_posts: Vec<PostSummary> = posts.into_iter().skip(start).take(per_page as usize).collect();
-            
-            let response = PostListResponse {
-                posts: paginated_posts,
-                total,
-                page,
-                per_page,
-            };
-            
-            (serde_json::to_string(&response).unwrap(), 200)
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-    // wrapper for POST that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn POST(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::POST(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-#[allow(non_snake_case)]
-mod module__home_maxerius_Code_framework_test_core_src_engine__________example_static_rs {
-    mod __orig {
-        // ✅ REAL EXAMPLE: Static File Serving with Caching
-        use std::collections::HashMap;
-        
-        // Note: This demonstrates the API, but actual async file serving
-        // requires integration into the async request handler
-        
-        pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-            use core::engine::StaticFileServer;
-            
-            // Get requested file path from params
-            let file_path = params.get("path")
-                .map(|s| s.as_str())
-                .unwrap_or("/index.html");
-            
-            println!("✅ Static file request: {}", file_path);
-            
-            // ✅ REAL FEATURE: StaticFileServer with caching and security
-            // In async context, you would do:
-            // let server = StaticFileServer::new("./public");
-            // match server.serve(file_path).await {
-            //     Ok(file) => {
-            //         // file.content: Bytes
-            //         // file.content_type: auto-detected from extension
-            //         return file.to_response();
-            //     }
-            //     Err(e) => {
-            //         return error_response(404, "File not found");
-            //     }
-            // }
-            
-            // Security features:
-            // ✅ Directory traversal protection (blocks ../)
-            // ✅ File type validation (only serves files, not directories)
-            // ✅ Content-Type auto-detection for 20+ file types
-            // ✅ In-memory caching with DashMap
-            
-            // Mock response showing what would be returned
-            let response = match file_path.split('.').last().unwrap_or("") {
-                "html" => {
-                    println!("   Content-Type: text/html");
-                    (r#"<!DOCTYPE html>
-        <html>
-        <head><title>Example</title></head>
-        <body><h1>Static HTML Page</h1></body>
-        </html>"#.to_string(), 200)
-                }
-                "css" => {
-                    println!("   Content-Type: text/css");
-                    ("body { font-family: sans-serif; }".to_string(), 200)
-                }
-                "js" => {
-                    println!("   Content-Type: application/javascript");
-                    ("console.log('Static JS loaded');".to_string(), 200)
-                }
-                "json" => {
-                    println!("   Content-Type: application/json");
-                    (r#"{"message": "Static JSON data"}"#.to_string(), 200)
-                }
-                "png" | "jpg" | "jpeg" => {
-                    println!("   Content-Type: image/{}",  file_path.split('.').last().unwrap());
-                    (r#"{"note": "Binary image data would be here"}"#.to_string(), 200)
-                }
-                _ => {
-                    println!("   ❌ File not found");
-                    (r#"{"error": "File not found"}"#.to_string(), 404)
-                }
-            };
-            
-            println!("   ✅ Cached for future requests");
-            println!("   ✅ Directory traversal protection active");
-            
-            response
-        }
-    }
-    // wrapper for GET that adapts (String,u16) -> Response
-    #[inline(always)]
-    pub fn GET(params: &std::collections::HashMap<String, String>) -> super::Response {
-        let (s, status) = __orig::GET(params);
-        super::Response { status, body: s.into_bytes().into(), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
-    }
-}
-
-use std::option::Option;
-
-pub type Handler = fn(&std::collections::HashMap<String, String>) -> super::Response;
-
-#[inline(always)]
-pub fn get_handler(route: &str, method: &str) -> Option<(Handler, std::collections::HashMap<String, String>)> {
-    // Fast path: pre-check method bytes for quick rejection
-    let method_bytes = method.as_bytes();
-    
-    // Normalize and split route into segments (stack-allocated for small routes)
-    let route_normalized = route.trim_start_matches('/').trim_end_matches('/');
-    let seg_count = if route_normalized.is_empty() { 0 } else { route_normalized.bytes().filter(|&b| b == b'/').count() + 1 };
-    
-    // Use small fixed arrays for common cases to avoid heap allocation
-    let mut seg_buf: [&str; 8] = [""; 8];
-    let segments = if seg_count <= 8 {
-        let mut i = 0;
-        for seg in route_normalized.split('/') {
-            if i >= 8 { break; }
-            seg_buf[i] = seg;
-            i += 1;
-        }
-        &seg_buf[..seg_count]
-    } else {
-        // Fallback to heap for deep routes
-        return None; // or handle with Vec if needed
-    };
-
-    // Match pattern: GET /main
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 1 {
-        if segments[0] != "main" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_main_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /api
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 1 {
-        if segments[0] != "api" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: POST /api
-    if method_bytes.len() == 4 && method_bytes == b"POST" && seg_count == 1 {
-        if segments[0] != "api" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_rs::POST, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /users/[id]
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 2 {
-        if segments[0] != "users" { /* skip */ } else
-        {
-            let mut params = std::collections::HashMap::with_capacity(1);
-            params.insert("id".to_string(), segments[1].to_string());
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_users__id__rs::GET, params));
-        }
-    }
-    // Match pattern: DELETE /users/[id]
-    if method_bytes.len() == 6 && method_bytes == b"DELETE" && seg_count == 2 {
-        if segments[0] != "users" { /* skip */ } else
-        {
-            let mut params = std::collections::HashMap::with_capacity(1);
-            params.insert("id".to_string(), segments[1].to_string());
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_users__id__rs::DELETE, params));
-        }
-    }
-    // Match pattern: GET /posts/[id]/comments/[commentId]
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 4 {
-        if segments[0] != "posts" { /* skip */ } else
-        if segments[2] != "comments" { /* skip */ } else
-        {
-            let mut params = std::collections::HashMap::with_capacity(2);
-            params.insert("id".to_string(), segments[1].to_string());
-            params.insert("commentId".to_string(), segments[3].to_string());
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_posts__id__comments__commentId__rs::GET, params));
-        }
-    }
-    // Match pattern: DELETE /posts/[id]/comments/[commentId]
-    if method_bytes.len() == 6 && method_bytes == b"DELETE" && seg_count == 4 {
-        if segments[0] != "posts" { /* skip */ } else
-        if segments[2] != "comments" { /* skip */ } else
-        {
-            let mut params = std::collections::HashMap::with_capacity(2);
-            params.insert("id".to_string(), segments[1].to_string());
-            params.insert("commentId".to_string(), segments[3].to_string());
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_posts__id__comments__commentId__rs::DELETE, params));
-        }
-    }
-    // Match pattern: GET /search
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 1 {
-        if segments[0] != "search" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_search_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /api/admin/users
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 3 {
-        if segments[0] != "api" { /* skip */ } else
-        if segments[1] != "admin" { /* skip */ } else
-        if segments[2] != "users" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_admin_users_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: POST /api/upload
-    if method_bytes.len() == 4 && method_bytes == b"POST" && seg_count == 2 {
-        if segments[0] != "api" { /* skip */ } else
-        if segments[1] != "upload" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_upload_rs::POST, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /api/public/data
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 3 {
-        if segments[0] != "api" { /* skip */ } else
-        if segments[1] != "public" { /* skip */ } else
-        if segments[2] != "data" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_public_data_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: OPTIONS /api/public/data
-    if method_bytes.len() == 7 && method_bytes == b"OPTIONS" && seg_count == 3 {
-        if segments[0] != "api" { /* skip */ } else
-        if segments[1] != "public" { /* skip */ } else
-        if segments[2] != "data" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_public_data_rs::OPTIONS, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /api/large-data
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 2 {
-        if segments[0] != "api" { /* skip */ } else
-        if segments[1] != "large-data" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_api_large-data_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /auth/login
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 2 {
-        if segments[0] != "auth" { /* skip */ } else
-        if segments[1] != "login" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_auth_login_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: POST /auth/login
-    if method_bytes.len() == 4 && method_bytes == b"POST" && seg_count == 2 {
-        if segments[0] != "auth" { /* skip */ } else
-        if segments[1] != "login" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_auth_login_rs::POST, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: DELETE /auth/login
-    if method_bytes.len() == 6 && method_bytes == b"DELETE" && seg_count == 2 {
-        if segments[0] != "auth" { /* skip */ } else
-        if segments[1] != "login" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_auth_login_rs::DELETE, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /posts
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 1 {
-        if segments[0] != "posts" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_posts_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: POST /posts
-    if method_bytes.len() == 4 && method_bytes == b"POST" && seg_count == 1 {
-        if segments[0] != "posts" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_posts_rs::POST, std::collections::HashMap::new()));
-        }
-    }
-    // Match pattern: GET /static
-    if method_bytes.len() == 3 && method_bytes == b"GET" && seg_count == 1 {
-        if segments[0] != "static" { /* skip */ } else
-        {
-            return Some((module__home_maxerius_Code_framework_test_core_src_engine__________example_static_rs::GET, std::collections::HashMap::new()));
-        }
-    }
-    None
-}
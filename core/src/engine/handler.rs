@@ -1,27 +1,52 @@
 // handle http requests and route them to the runtime
 use crate::engine::runtime::Runtime;
-use crate::engine::parser::{ProjectFile, RouteSegment};
-use tokio::fs;
-use std::collections::HashMap;
+use crate::engine::parser::{pattern_segments, RouteSegment};
+use crate::engine::request::Request;
+use crate::engine::errors::FrameworkError;
+use crate::engine::middleware::RateLimiter;
+use crate::engine::route_config::{Auth, RouteConfig};
+use crate::engine::auth::AuthVerifier;
+use crate::engine::state::AppState;
+use crate::engine::route_table::{AfterHook, BeforeHook, ErrorPage, ErrorPages, Middleware, RouteHandler, RouteMatch, RouteTable};
+use crate::engine::router::{precedes, Router};
+use crate::engine::dev_routes::{routes_page, DEV_ROUTES_PATH};
 use std::sync::Arc;
 use dashmap::DashMap;
 use percent_encoding::percent_decode_str;
 use bytes::Bytes;
-use ahash::AHashMap;
 use smallvec::SmallVec;
 use once_cell::sync::Lazy;
+use std::time::Instant;
 
 // Type alias for route params - uses SmallVec for stack allocation when <= 4 params
-type RouteParams = SmallVec<[(String, String); 4]>;
+pub(crate) type RouteParams = SmallVec<[(String, String); 4]>;
+
+/// Response cache key: the route id, the path and the sorted query pairs
+type ResponseCacheKey = (usize, String, Vec<(String, String)>);
+
+/// Most responses kept in the response cache; expired entries are swept when it's full
+const RESPONSE_CACHE_LIMIT: usize = 1024;
 
 // Common path strings to avoid allocations
 static HEALTH_PATH: &str = "/health";
-static ROOT_PATH: &str = "/";
-
-// Include the compile-time generated router (created by build.rs)
-include!("generated_routes.rs");
 
 // Static responses to avoid allocations
+/// Swagger UI (from a CDN) for the OpenAPI document at `{spec}`
+const SWAGGER_UI_PAGE: &str = r##"<!doctype html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>API docs</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>window.ui = SwaggerUIBundle({ url: "{spec}", dom_id: "#swagger-ui" });</script>
+</body>
+</html>
+"##;
+
 static HEALTH_RESPONSE: Lazy<super::Response> = Lazy::new(|| super::Response {
     status: 200,
     body: Bytes::from_static(b"OK"),
@@ -36,6 +61,13 @@ static NOT_FOUND_RESPONSE: Lazy<super::Response> = Lazy::new(|| super::Response
     headers: Vec::new(),
 });
 
+static METHOD_NOT_ALLOWED_RESPONSE: Lazy<super::Response> = Lazy::new(|| super::Response {
+    status: 405,
+    body: Bytes::from_static(b"Method Not Allowed"),
+    content_type: "text/plain; charset=utf-8",
+    headers: Vec::new(),
+});
+
 static BAD_REQUEST_RESPONSE: Lazy<super::Response> = Lazy::new(|| super::Response {
     status: 400,
     body: Bytes::from_static(b"Bad Request"),
@@ -50,12 +82,7 @@ static INTERNAL_ERROR_RESPONSE: Lazy<super::Response> = Lazy::new(|| super::Resp
     headers: Vec::new(),
 });
 
-pub struct Request {
-    pub path: String,
-    pub method: String,
-    pub body: Option<String>,
-}
-
+#[derive(Clone, Debug)]
 pub struct Response {
     pub status: u16,
     pub body: Bytes,
@@ -66,29 +93,63 @@ pub struct Response {
 #[derive(Clone, Debug)]
 pub struct RequestHandler {
     pub runtime: Arc<Runtime>,
-    /// Simple in-memory route lookup cache: (method,path) -> file_path (Arc to avoid cloning large strings)
-    pub route_cache: Arc<DashMap<String, Option<Arc<String>>>>,
-    /// Cache matched params for a (method,path) so we don't re-run match_route on cache hits.
-    /// Using SmallVec for better cache locality and stack allocation
-    pub route_params_cache: Arc<DashMap<String, Option<Arc<RouteParams>>>>,
+    /// The app's generated routes
+    pub routes: &'static RouteTable,
+    /// Rate limiter of each route id whose `ROUTE` sets `rate_limit`
+    pub rate_limiters: Arc<Vec<Option<RateLimiter>>>,
+    /// GET responses of routes with a `cache_ttl`, by route id + path + query, with their expiry.
+    /// Holds at most `RESPONSE_CACHE_LIMIT` entries.
+    pub response_cache: Arc<DashMap<ResponseCacheKey, (Instant, super::Response)>>,
+    /// The app state from `_state.rs`, built once and handed to every request
+    pub state: Arc<AppState>,
+    /// Routes registered in code, served next to the route files
+    pub router: Arc<Router>,
+    /// Route segments of every route file handler by route id, to rank them against `router`
+    pub file_segments: Arc<Vec<Vec<RouteSegment>>>,
 }
 
 impl RequestHandler {
     pub fn new(runtime: &Runtime) -> Self {
+        let routes = runtime.routes;
         RequestHandler {
             runtime: Arc::new(runtime.clone()),
-            route_cache: Arc::new(DashMap::new()),
-            route_params_cache: Arc::new(DashMap::new()),
+            routes,
+            rate_limiters: Arc::new(routes.configs.iter()
+                .map(|c| c.rate_limit.map(|limit| RateLimiter::with_window(limit.requests, limit.per)))
+                .collect()),
+            response_cache: Arc::new(DashMap::new()),
+            state: Arc::new((routes.app_state)()),
+            router: Arc::new(Router::new()),
+            file_segments: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Serve the routes of `router` next to the route files, unless they conflict (see
+    /// [`Router::check`]).
+    pub fn with_router(mut self, router: Router) -> Result<Self, Vec<String>> {
+        let errors = router.check(self.routes);
+        if !errors.is_empty() {
+            return Err(errors);
         }
+        self.file_segments = Arc::new(self.routes.routes.iter().map(|r| pattern_segments(r.pattern)).collect());
+        self.router = Arc::new(router);
+        Ok(self)
+    }
+
+    /// Replace the app state, e.g. with test doubles
+    pub fn with_state(mut self, state: AppState) -> Self {
+        self.state = Arc::new(state);
+        self
     }
 
-    /// Handle a request asynchronously and return a raw HTTP response string.
+    /// Handle a request asynchronously and return the structured response.
     #[inline]
-    pub async fn handle_request(&self, method: &str, raw_path: &str) -> super::Response {
-        let method = method.to_string();
+    pub async fn handle_request(&self, mut req: Request) -> super::Response {
+        req.state = self.state.clone();
+        let method = req.method.clone();
 
-        // Basic sanitization and decode path
-        let path = sanitize_and_decode_path(raw_path);
+        // Basic sanitization; segments are percent-decoded individually by the router
+        let path = sanitize_path(&req.path);
         if method.eq_ignore_ascii_case("GET") && path == HEALTH_PATH {
             return super::Response {
                 status: HEALTH_RESPONSE.status,
@@ -98,102 +159,292 @@ impl RequestHandler {
             };
         }
 
-        // Try compile-time generated router first - now returns (handler, params)
-        if let Some((h, params)) = get_handler(&path, &method) {
-            // Use the extracted params from the router directly
-            let resp = h(&params);
-            return resp;
+        // The generated OpenAPI document, and its Swagger UI page and the route explorer in dev mode
+        if method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD") {
+            if self.routes.openapi_path == Some(path.as_str()) {
+                return super::Response {
+                    status: 200,
+                    body: Bytes::from_static(self.routes.openapi_json.as_bytes()),
+                    content_type: "application/json",
+                    headers: Vec::new(),
+                };
+            }
+            if self.runtime.dev && self.routes.swagger_ui_path == Some(path.as_str()) && let Some(spec) = self.routes.openapi_path {
+                return super::Response {
+                    status: 200,
+                    body: Bytes::from(SWAGGER_UI_PAGE.replace("{spec}", spec)),
+                    content_type: "text/html; charset=utf-8",
+                    headers: Vec::new(),
+                };
+            }
+            if self.runtime.dev && path == DEV_ROUTES_PATH {
+                return super::Response {
+                    status: 200,
+                    body: Bytes::from(routes_page(self.routes, &self.router)),
+                    content_type: "text/html; charset=utf-8",
+                    headers: Vec::new(),
+                };
+            }
+        }
+
+        // Look the route up in the compile-time match tree and the `Router` - returns the handler
+        // and extracted params. HEAD falls back to the GET handler; the server drops the body
+        // but keeps its length.
+        let matched = self.find_route(&path, &method).or_else(|| {
+            if method.eq_ignore_ascii_case("HEAD") { self.find_route(&path, "GET") } else { None }
+        });
+        if let Some(matched) = matched {
+            // Hand the extracted params to the handler along with the rest of the request
+            req.params = matched.params;
+            req.catch_all = matched.catch_all;
+            let (id, handler, config, pages) = (matched.id, matched.handler, matched.config, matched.error_pages);
+            return run_middleware(matched.middleware, &req, async {
+                match self.check_route_config(id, config, &req) {
+                    Ok(()) => self.call_handler(id, handler, config, pages, &req).await,
+                    Err(err) => render_error_page(pages, &req, &err).await,
+                }
+            }).await;
+        }
+
+        // The path exists but not for this method: answer OPTIONS, otherwise 405. These still
+        // go through the path's middleware (e.g. so CORS headers reach preflight requests).
+        let mut allowed = (self.routes.allowed_methods)(&path);
+        for m in self.router.allowed_methods(&path) {
+            if !allowed.contains(&m) {
+                allowed.push(m);
+            }
+        }
+        if !allowed.is_empty() {
+            let allow = allow_header(&allowed);
+            let route = self.find_route(&path, allowed[0]);
+            let middleware = route.as_ref().map_or(&[][..], |m| m.middleware);
+            let response = if method.eq_ignore_ascii_case("OPTIONS") {
+                super::Response {
+                    status: 204,
+                    body: Bytes::new(),
+                    content_type: "text/plain; charset=utf-8",
+                    headers: vec![("Allow".to_string(), allow)],
+                }
+            } else {
+                let err = FrameworkError::InvalidMethod { method: method.clone() };
+                let mut response = match route {
+                    Some(route) => render_error_page(route.error_pages, &req, &err).await,
+                    None => default_error_response(&err),
+                };
+                if !response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("allow")) {
+                    response.headers.push(("Allow".to_string(), allow));
+                }
+                response
+            };
+            return run_middleware(middleware, &req, async { response }).await;
+        }
+
+        self.render_error(&req, FrameworkError::RouteNotFound { path }).await
+    }
+
+    /// The route for a path + method: the route file's or the `Router`'s, whichever the match
+    /// tree would try first. Router routes come after the route files in the id space, and a
+    /// tie goes to the route file.
+    fn find_route(&self, path: &str, method: &str) -> Option<RouteMatch> {
+        let file = (self.routes.get_handler)(path, method);
+        let Some((index, route, handler, params)) = self.router.find(path, method) else {
+            return file;
+        };
+        if file.as_ref().is_some_and(|f| !precedes(&route.segments, &self.file_segments[f.id])) {
+            return file;
+        }
+        // The segments under a trailing catch-all, decoded one by one like the match tree does
+        let catch_all = match route.segments.last() {
+            Some(RouteSegment::CatchAll(_) | RouteSegment::OptionalCatchAll(_)) => path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .skip(route.segments.len() - 1)
+                .map(|s| percent_decode_str(s).decode_utf8_lossy().into_owned())
+                .collect(),
+            _ => Vec::new(),
+        };
+        Some(RouteMatch {
+            handler,
+            params: params.into_iter().collect(),
+            catch_all,
+            middleware: &[],
+            error_pages: error_pages_for_path(self.routes, path),
+            config: &RouteConfig::DEFAULT,
+            id: self.routes.configs.len() + index,
+        })
+    }
+
+    /// Enforce the route's `ROUTE` settings that are checked before its handler runs.
+    fn check_route_config(&self, id: usize, config: &RouteConfig, req: &Request) -> Result<(), FrameworkError> {
+        // Only route files have rate limits; `Router` routes are past the end
+        if let Some(Some(limiter)) = self.rate_limiters.get(id) {
+            let client = req.remote_addr.map(|addr| addr.ip().to_string()).unwrap_or_default();
+            if !limiter.check_limit(&client) {
+                return Err(FrameworkError::TooManyRequests { retry_after: limiter.retry_after() });
+            }
+        }
+        if config.auth != Auth::None {
+            let authorization = req.header("authorization").map_or("", String::as_str);
+            let scheme = if config.auth == Auth::Bearer { "Bearer" } else { "Basic" };
+            match req.state.get::<AuthVerifier>().and_then(|verifier| verifier.verify(config.auth, authorization)) {
+                Some(true) => {}
+                Some(false) => return Err(FrameworkError::Unauthorized { scheme }),
+                None => return Err(FrameworkError::InternalError(format!("no `AuthVerifier` in the app state checks {} credentials", scheme))),
+            }
+        }
+        if let Some(limit) = config.max_body && req.body.len() > limit {
+            return Err(FrameworkError::PayloadTooLarge { limit });
+        }
+        Ok(())
+    }
+
+    /// Call a route handler, within the route's `timeout` and through its response cache.
+    /// Errors from its extractors are rendered with the route's error pages.
+    async fn call_handler(&self, id: usize, handler: RouteHandler, config: &RouteConfig, pages: ErrorPages, req: &Request) -> super::Response {
+        // HEAD is answered by the GET handler, so it shares the cached GET responses. Requests
+        // with credentials may get a response meant for them only, so they bypass the cache.
+        let personal = req.header("authorization").is_some() || req.header("cookie").is_some();
+        let cache_key = config.cache_ttl
+            .filter(|_| req.method.eq_ignore_ascii_case("GET") || req.method.eq_ignore_ascii_case("HEAD"))
+            .filter(|_| !personal)
+            .map(|_| response_cache_key(id, req));
+        if let Some(key) = &cache_key {
+            match self.response_cache.get(key) {
+                Some(entry) if entry.0 > Instant::now() => return entry.1.clone(),
+                Some(entry) => {
+                    drop(entry);
+                    self.response_cache.remove(key);
+                }
+                None => {}
+            }
+        }
+
+        let result = match handler {
+            RouteHandler::Sync(f) => f(req),
+            RouteHandler::Async(f) => match config.timeout {
+                Some(limit) => tokio::time::timeout(limit, f(req)).await
+                    .unwrap_or(Err(FrameworkError::Timeout { ms: limit.as_millis() })),
+                None => f(req).await,
+            },
+        };
+        // Rejected by an extractor (or timed out)
+        let mut response = match result {
+            Ok(response) => response,
+            Err(err) => return render_error_page(pages, req, &err).await,
+        };
+
+        if let Some(ttl) = config.cache_ttl && response.status == 200
+            && !response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("cache-control"))
+        {
+            let scope = if personal { "private" } else { "public" };
+            response.headers.push(("Cache-Control".to_string(), format!("{}, max-age={}", scope, ttl.as_secs())));
         }
+        if let (Some(ttl), Some(key)) = (config.cache_ttl, cache_key) && response.status == 200 {
+            self.cache_response(key, Instant::now() + ttl, &response);
+        }
+        response
+    }
 
-        // fallback: serve registered files directly (useful during development)
-        if method.eq_ignore_ascii_case("GET") {
-            for file in &self.runtime.project_files {
-                if let Some(params) = match_route_fast(file, &path) {
-                    // Security: prefer cached contents in production
-                    if !self.runtime.dev {
-                        if let Some(cached) = self.runtime.file_cache.get(&file.full_path) {
-                            let contents = cached.clone();
-                            if params.is_empty() {
-                                return super::Response {
-                                    status: 200,
-                                    body: contents,
-                                    content_type: content_type_for_path(&file.full_path),
-                                    headers: Vec::new(),
-                                };
-                            } else {
-                                // need to prepend params text
-                                let mut v = Vec::new();
-                                let mut params_text = String::from("\nParams:\n");
-                                for (k, v_) in &params {
-                                    params_text.push_str(&format!("{} = {}\n", k, v_));
-                                }
-                                v.extend_from_slice(params_text.as_bytes());
-                                v.extend_from_slice(&contents);
-                                return super::Response {
-                                    status: 200,
-                                    body: Bytes::from(v),
-                                    content_type: content_type_for_path(&file.full_path),
-                                    headers: Vec::new(),
-                                };
-                            }
-                        }
-                    }
-
-                    // fallback: async read from filesystem (dev mode or cache miss)
-                    match fs::read(&file.full_path).await {
-                        Ok(contents_vec) => {
-                            if params.is_empty() {
-                                return super::Response {
-                                    status: 200,
-                                    body: Bytes::from(contents_vec),
-                                    content_type: content_type_for_path(&file.full_path),
-                                    headers: Vec::new(),
-                                };
-                            } else {
-                                let mut v = Vec::new();
-                                let mut params_text = String::from("\nParams:\n");
-                                for (k, v_) in &params {
-                                    params_text.push_str(&format!("{} = {}\n", k, v_));
-                                }
-                                v.extend_from_slice(params_text.as_bytes());
-                                v.extend_from_slice(&contents_vec);
-                                return super::Response {
-                                    status: 200,
-                                    body: Bytes::from(v),
-                                    content_type: content_type_for_path(&file.full_path),
-                                    headers: Vec::new(),
-                                };
-                            }
-                        }
-                        Err(e) => {
-                            let body = format!("Failed to read file: {}", e);
-                            return super::Response {
-                                status: 500,
-                                body: Bytes::from(body.into_bytes()),
-                                content_type: "text/plain; charset=utf-8",
-                                headers: Vec::new(),
-                            };
-                        }
-                    }
+    /// Keep a response until `expires`. A full cache first drops its expired entries, then the
+    /// one closest to expiring.
+    fn cache_response(&self, key: ResponseCacheKey, expires: Instant, response: &super::Response) {
+        if self.response_cache.len() >= RESPONSE_CACHE_LIMIT {
+            let now = Instant::now();
+            self.response_cache.retain(|_, (expiry, _)| *expiry > now);
+            if self.response_cache.len() >= RESPONSE_CACHE_LIMIT {
+                let oldest = self.response_cache.iter().min_by_key(|entry| entry.value().0).map(|entry| entry.key().clone());
+                if let Some(oldest) = oldest {
+                    self.response_cache.remove(&oldest);
                 }
             }
         }
+        self.response_cache.insert(key, (expires, response.clone()));
+    }
+
+    /// Render an error for a request that didn't reach a route handler, through the nearest
+    /// `_404.rs` / `_405.rs` / `_error.rs` for its path.
+    pub async fn render_error(&self, req: &Request, err: FrameworkError) -> super::Response {
+        let path = sanitize_path(&req.path);
+        render_error_page(error_pages_for_path(self.routes, &path), req, &err).await
+    }
+}
+
+/// Render `err` with the page in `pages` for its status (`_404.rs`, `_405.rs`, otherwise
+/// `_error.rs`), or the built-in response when there is none.
+async fn render_error_page(pages: ErrorPages, req: &Request, err: &FrameworkError) -> super::Response {
+    let page = match err.status_code() {
+        404 => pages.not_found,
+        405 => pages.method_not_allowed,
+        _ => None,
+    };
+    let mut response = match page.or(pages.error) {
+        Some(ErrorPage::Sync(f)) => f(req, err),
+        Some(ErrorPage::Async(f)) => f(req, err).await,
+        None => default_error_response(err),
+    };
+    // Headers the status calls for, unless the page set them itself
+    let header = match err {
+        FrameworkError::Unauthorized { scheme } => Some(("WWW-Authenticate", scheme.to_string())),
+        FrameworkError::TooManyRequests { retry_after } => Some(("Retry-After", retry_after.to_string())),
+        _ => None,
+    };
+    if let Some((name, value)) = header && !response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(name)) {
+        response.headers.push((name.to_string(), value));
+    }
+    response
+}
 
-        super::Response {
-            status: NOT_FOUND_RESPONSE.status,
-            body: NOT_FOUND_RESPONSE.body.clone(),
-            content_type: NOT_FOUND_RESPONSE.content_type,
-            headers: Vec::new(),
+/// Response cache key of a request: the route id, the path and the sorted (decoded) query pairs
+fn response_cache_key(id: usize, req: &Request) -> ResponseCacheKey {
+    let mut query: Vec<(String, String)> = req.query.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    query.sort();
+    (id, req.path.clone(), query)
+}
+
+fn default_error_response(err: &FrameworkError) -> super::Response {
+    let fixed = match err.status_code() {
+        400 => &BAD_REQUEST_RESPONSE,
+        404 => &NOT_FOUND_RESPONSE,
+        405 => &METHOD_NOT_ALLOWED_RESPONSE,
+        500 => &INTERNAL_ERROR_RESPONSE,
+        status => {
+            return super::Response {
+                status,
+                body: Bytes::from_static(status_text(status).as_bytes()),
+                content_type: "text/plain; charset=utf-8",
+                headers: Vec::new(),
+            };
         }
+    };
+    super::Response {
+        status: fixed.status,
+        body: fixed.body.clone(),
+        content_type: fixed.content_type,
+        headers: Vec::new(),
     }
 }
 
-/// Match a request path against precomputed route segments from a ProjectFile.
-/// Returns SmallVec of params for better cache locality. Optimized with precomputed segments.
+/// Error pages for a path that matched no route: those of the deepest directory whose
+/// URL prefix matches it.
+fn error_pages_for_path(routes: &RouteTable, path: &str) -> ErrorPages {
+    let segs: SmallVec<[std::borrow::Cow<'_, str>; 8]> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| percent_decode_str(s).decode_utf8_lossy())
+        .collect();
+    let pages = routes.path_error_pages;
+    pages
+        .iter()
+        .find(|(prefix, _)| {
+            prefix.len() <= segs.len() && prefix.iter().zip(&segs).all(|(p, s)| p.is_none_or(|p| p == s))
+        })
+        .map_or(pages[pages.len() - 1].1, |(_, pages)| *pages)
+}
+
+/// Match a request path against precomputed route segments, as `Router` does for its routes.
+/// Returns SmallVec of params for better cache locality.
 #[inline(always)]
-fn match_route_fast(file: &crate::engine::parser::ProjectFile, req_path: &str) -> Option<RouteParams> {
-    use crate::engine::parser::RouteSegment;
-    
+pub(crate) fn match_segments(segments: &[RouteSegment], req_path: &str) -> Option<RouteParams> {
     // strip query string
     let req = req_path.split('?').next().unwrap_or("").trim();
     let req = if req == "/" {
@@ -208,17 +459,28 @@ fn match_route_fast(file: &crate::engine::parser::ProjectFile, req_path: &str) -
         req.split('/').collect()
     };
 
-    if file.route_segments.len() != req_segments.len() {
-        return None;
+    // A trailing catch-all segment absorbs the rest of the path
+    let (fixed, catch_all) = match segments.last() {
+        Some(RouteSegment::CatchAll(name)) => (&segments[..segments.len() - 1], Some((name, 1))),
+        Some(RouteSegment::OptionalCatchAll(name)) => (&segments[..segments.len() - 1], Some((name, 0))),
+        _ => (segments, None),
+    };
+    match catch_all {
+        None if fixed.len() != req_segments.len() => return None,
+        Some((_, min_rest)) if req_segments.len() < fixed.len() + min_rest => return None,
+        _ => {}
     }
 
     // Use SmallVec for stack allocation when <= 4 params
     let mut params = RouteParams::new();
-    for (route_seg, rseg) in file.route_segments.iter().zip(req_segments.iter()) {
+    for (route_seg, rseg) in fixed.iter().zip(req_segments.iter()) {
         match route_seg {
-            RouteSegment::Dynamic(name) => {
+            RouteSegment::Dynamic(name, constraint) => {
                 // decode percent-encoding in rseg
                 let decoded = percent_decode_str(rseg).decode_utf8_lossy().to_string();
+                if constraint.as_ref().is_some_and(|c| !c.matches(&decoded)) {
+                    return None;
+                }
                 params.push((name.clone(), decoded));
             }
             RouteSegment::Static(expected) => {
@@ -227,81 +489,90 @@ fn match_route_fast(file: &crate::engine::parser::ProjectFile, req_path: &str) -
                     return None;
                 }
             }
+            RouteSegment::CatchAll(_) | RouteSegment::OptionalCatchAll(_) => return None,
         }
     }
+    if let Some((name, _)) = catch_all {
+        let rest: Vec<String> = req_segments[fixed.len()..]
+            .iter()
+            .map(|s| percent_decode_str(s).decode_utf8_lossy().to_string())
+            .collect();
+        params.push((name.clone(), rest.join("/")));
+    }
 
     Some(params)
 }
 
-/// Legacy match_route for backward compatibility (uses file_path string)
-#[inline]
-fn match_route(file_fp: &str, req_path: &str) -> Option<RouteParams> {
-    // strip query string
-    let req = req_path.split('?').next().unwrap_or("").trim();
-    let req = if req == "/" {
-        ""
-    } else {
-        req.trim_start_matches('/').trim_end_matches('/')
-    };
-
-    // Normalize file path to route segments
-    let fp = file_fp.trim();
-    // handle index.rs specially
-    let route_fp = if fp.ends_with("index.rs") {
-        let dir = fp.trim_end_matches("index.rs").trim_end_matches('/');
-        dir.trim_end_matches('/')
-    } else {
-        fp.trim_end_matches(".rs")
-    };
+/// Run `endpoint` inside a route's middleware chain. `before` hooks run outermost first and
+/// the first one returning a response short-circuits; `after` hooks then run innermost first
+/// for every layer whose `before` let the request through.
+async fn run_middleware(
+    middleware: &[Middleware],
+    req: &Request,
+    endpoint: impl std::future::Future<Output = super::Response>,
+) -> super::Response {
+    let mut passed = 0;
+    let mut early = None;
+    for mw in middleware {
+        let response = match mw.before {
+            Some(BeforeHook::Sync(f)) => f(req),
+            Some(BeforeHook::Async(f)) => f(req).await,
+            None => None,
+        };
+        if response.is_some() {
+            early = response;
+            break;
+        }
+        passed += 1;
+    }
 
-    let fp_segments: Vec<&str> = if route_fp.is_empty() {
-        Vec::new()
-    } else {
-        route_fp.split('/').collect()
+    let mut response = match early {
+        Some(response) => response,
+        None => endpoint.await,
     };
-    let req_segments: Vec<&str> = if req.is_empty() {
-        Vec::new()
-    } else {
-        req.split('/').collect()
-    };
-
-    if fp_segments.len() != req_segments.len() {
-        return None;
+    for mw in middleware[..passed].iter().rev() {
+        response = match mw.after {
+            Some(AfterHook::Sync(f)) => f(req, response),
+            Some(AfterHook::Async(f)) => f(req, response).await,
+            None => response,
+        };
     }
+    response
+}
 
-    let mut params = RouteParams::new();
-    for (fseg, rseg) in fp_segments.iter().zip(req_segments.iter()) {
-        // decode percent-encoding in rseg
-        let decoded = percent_decode_str(rseg).decode_utf8_lossy().to_string();
-        if fseg.starts_with('[') && fseg.ends_with(']') {
-            let name = &fseg[1..fseg.len() - 1];
-            params.push((name.to_string(), decoded));
-            continue;
-        }
-        if fseg != &decoded {
-            return None;
+/// Build the `Allow` header for a path: the methods it has handlers for, plus HEAD
+/// (derived from GET) and OPTIONS (always answered).
+fn allow_header(methods: &[&str]) -> String {
+    let mut allow: Vec<&str> = Vec::with_capacity(methods.len() + 2);
+    for m in methods {
+        allow.push(m);
+        if *m == "GET" && !methods.contains(&"HEAD") {
+            allow.push("HEAD");
         }
     }
-
-    Some(params)
+    if !methods.contains(&"OPTIONS") {
+        allow.push("OPTIONS");
+    }
+    allow.join(", ")
 }
 
 #[inline(always)]
-fn sanitize_and_decode_path(p: &str) -> String {
-    // remove any trailing/leading whitespace, disallow \0, and decode percent-encoding
+fn sanitize_path(p: &str) -> String {
+    // remove any trailing/leading whitespace and disallow \0
     let p = p.trim().split('\0').next().unwrap_or("");
     // Only keep path component before query
     let before_q = p.split('?').next().unwrap_or("");
-    let decoded = percent_decode_str(before_q).decode_utf8_lossy();
-    
-    // collapse // and remove .. segments for basic traversal protection
-    // Preallocate capacity based on decoded length
+
+    // collapse // and remove .. segments for basic traversal protection. Segments stay
+    // percent-encoded (the router decodes each one on its own so `%2F` never splits a
+    // segment); they are only decoded here to catch encoded `.`/`..`.
     let mut parts = Vec::with_capacity(8); // typical depth
-    for seg in decoded.split('/') {
-        if seg.is_empty() || seg == "." {
+    for seg in before_q.split('/') {
+        let decoded = percent_decode_str(seg).decode_utf8_lossy();
+        if seg.is_empty() || decoded == "." {
             continue;
         }
-        if seg == ".." {
+        if decoded == ".." {
             parts.pop();
             continue;
         }
@@ -322,52 +593,118 @@ fn sanitize_and_decode_path(p: &str) -> String {
     result
 }
 
-#[inline(always)]
-fn content_type_for_path(path: &str) -> &'static str {
-    // Optimized: check last few chars to avoid full string scan
-    let bytes = path.as_bytes();
-    let len = bytes.len();
-    
-    if len >= 5 {
-        match &bytes[len-5..] {
-            b".html" => return "text/html; charset=utf-8",
-            b".json" => return "application/json; charset=utf-8",
-            b".jpeg" => return "image/jpeg",
-            _ => {}
-        }
-    }
-    
-    if len >= 4 {
-        match &bytes[len-4..] {
-            b".htm" => return "text/html; charset=utf-8",
-            b".css" => return "text/css; charset=utf-8",
-            b".svg" => return "image/svg+xml",
-            b".png" => return "image/png",
-            b".jpg" => return "image/jpeg",
-            _ => {}
-        }
-    }
-    
-    if len >= 3 && &bytes[len-3..] == b".js" {
-        return "application/javascript; charset=utf-8";
-    }
-    
-    "text/plain; charset=utf-8"
-}
-
 #[inline]
 pub(crate) fn status_text(code: u16) -> &'static str {
     match code {
+        100 => "Continue",
+        101 => "Switching Protocols",
         200 => "OK",
         201 => "Created",
+        202 => "Accepted",
+        203 => "Non-Authoritative Information",
         204 => "No Content",
+        205 => "Reset Content",
+        206 => "Partial Content",
+        300 => "Multiple Choices",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
         400 => "Bad Request",
         401 => "Unauthorized",
+        402 => "Payment Required",
         403 => "Forbidden",
         404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        407 => "Proxy Authentication Required",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        417 => "Expectation Failed",
+        418 => "I'm a teapot",
+        421 => "Misdirected Request",
+        422 => "Unprocessable Entity",
+        423 => "Locked",
+        424 => "Failed Dependency",
+        425 => "Too Early",
+        426 => "Upgrade Required",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        451 => "Unavailable For Legal Reasons",
         500 => "Internal Server Error",
+        501 => "Not Implemented",
         502 => "Bad Gateway",
         503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        506 => "Variant Also Negotiates",
+        507 => "Insufficient Storage",
+        508 => "Loop Detected",
+        510 => "Not Extended",
+        511 => "Network Authentication Required",
         _ => "",
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_all_matching() {
+        let segments = [
+            RouteSegment::Static("docs".to_string()),
+            RouteSegment::CatchAll("slug".to_string()),
+        ];
+        let params = match_segments(&segments, "/docs/guides/routing").unwrap();
+        assert_eq!(params[0], ("slug".to_string(), "guides/routing".to_string()));
+        assert!(match_segments(&segments, "/docs").is_none());
+    }
+
+    #[test]
+    fn test_optional_catch_all_matching() {
+        let segments = [
+            RouteSegment::Static("docs".to_string()),
+            RouteSegment::OptionalCatchAll("slug".to_string()),
+        ];
+        let params = match_segments(&segments, "/docs").unwrap();
+        assert_eq!(params[0], ("slug".to_string(), String::new()));
+        assert!(match_segments(&segments, "/other/page").is_none());
+    }
+
+    #[test]
+    fn test_typed_param_matching() {
+        use crate::engine::parser::ParamConstraint;
+        let segments = [
+            RouteSegment::Static("orders".to_string()),
+            RouteSegment::Dynamic("id".to_string(), Some(ParamConstraint::Type("u64".to_string()))),
+        ];
+        let params = match_segments(&segments, "/orders/42").unwrap();
+        assert_eq!(params[0], ("id".to_string(), "42".to_string()));
+        assert!(match_segments(&segments, "/orders/abc").is_none());
+    }
+
+    #[test]
+    fn test_response_cache_key() {
+        let key = |target: &str| response_cache_key(3, &Request::new("GET".to_string(), target.to_string()));
+        assert_ne!(key("/search?a=1%26b%3D2"), key("/search?a=1&b=2"));
+        assert_eq!(key("/search?b=2&a=1"), key("/search?a=1&b=2"));
+        assert_ne!(key("/search"), key("/search?q="));
+    }
+
+    #[test]
+    fn test_allow_header() {
+        assert_eq!(allow_header(&["GET", "POST"]), "GET, HEAD, POST, OPTIONS");
+        assert_eq!(allow_header(&["DELETE", "OPTIONS"]), "DELETE, OPTIONS");
+    }
+}
@@ -3,6 +3,9 @@ use bytes::Bytes;
 use std::io::Write;
 use flate2::Compression;
 use flate2::write::GzEncoder;
+use dashmap::DashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Compression utilities
 pub struct ResponseCompressor;
@@ -88,23 +91,60 @@ impl QueryParser {
     }
 }
 
-/// Rate limiter (simple token bucket implementation)
+/// Rate limiter: a token bucket per client that holds `requests` tokens and refills them
+/// over `per`. Buckets idle for a whole `per` are full again and get dropped, at most once
+/// per `per`, so clients that stop sending (or rotate addresses) don't stay in memory.
+#[derive(Debug)]
 pub struct RateLimiter {
-    // In a real implementation, this would use DashMap with timestamps
-    // For now, just a placeholder structure
+    requests: u32,
+    per: Duration,
+    buckets: DashMap<String, (f64, Instant)>,
+    last_sweep: Mutex<Instant>,
 }
 
 impl RateLimiter {
-    pub fn new(_requests_per_second: u32) -> Self {
-        Self {}
+    pub fn new(requests_per_second: u32) -> Self {
+        Self::with_window(requests_per_second, Duration::from_secs(1))
     }
-    
-    /// Check if request should be allowed (always true for now)
+
+    /// At most `requests` requests per client within `per`
+    pub fn with_window(requests: u32, per: Duration) -> Self {
+        Self { requests, per, buckets: DashMap::new(), last_sweep: Mutex::new(Instant::now()) }
+    }
+
+    /// Take a token for `client`; false when it has none left
     #[inline]
-    pub fn check_limit(&self, _client_ip: &str) -> bool {
-        // TODO: Implement actual rate limiting with token bucket
+    pub fn check_limit(&self, client_ip: &str) -> bool {
+        let now = Instant::now();
+        self.sweep(now);
+        let capacity = self.requests as f64;
+        let mut bucket = self.buckets.entry(client_ip.to_string()).or_insert((capacity, now));
+        let (tokens, last) = *bucket;
+        let refill = now.duration_since(last).as_secs_f64() / self.per.as_secs_f64() * capacity;
+        let tokens = (tokens + refill).min(capacity);
+        if tokens < 1.0 {
+            *bucket = (tokens, now);
+            return false;
+        }
+        *bucket = (tokens - 1.0, now);
         true
     }
+
+    /// Drop the buckets nobody has used within `per`; skipped while another thread is sweeping
+    fn sweep(&self, now: Instant) {
+        let Ok(mut last_sweep) = self.last_sweep.try_lock() else { return };
+        if now.duration_since(*last_sweep) < self.per {
+            return;
+        }
+        *last_sweep = now;
+        self.buckets.retain(|_, (_, last)| now.duration_since(*last) < self.per);
+    }
+
+    /// How long until a rejected client gets a token back (rounded up to whole seconds for
+    /// `Retry-After`)
+    pub fn retry_after(&self) -> u64 {
+        (self.per.as_secs_f64() / self.requests.max(1) as f64).ceil().max(1.0) as u64
+    }
 }
 
 /// CORS middleware
@@ -179,4 +219,18 @@ mod tests {
         let compressed_data = compressed.unwrap();
         assert!(compressed_data.len() < data.len());
     }
+
+    #[test]
+    fn test_rate_limiter_drops_idle_buckets() {
+        let limiter = RateLimiter::with_window(1, Duration::from_millis(20));
+        assert!(limiter.check_limit("10.0.0.1"));
+        assert!(!limiter.check_limit("10.0.0.1"));
+        assert!(limiter.check_limit("10.0.0.2"));
+        assert_eq!(limiter.buckets.len(), 2);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.check_limit("10.0.0.3"));
+        assert_eq!(limiter.buckets.len(), 1);
+        assert!(limiter.check_limit("10.0.0.1"));
+    }
 }
@@ -1,15 +1,38 @@
 pub mod parser;
 
-pub use parser::{parse_project_files, RouteSegment, ProjectFile};
+pub use parser::{RouteSegment, ParamConstraint};
 
 pub mod runtime;
 
 pub mod server;
 pub use server::Server;
 
+#[cfg(feature = "use_hyper")]
+pub mod server_hyper;
+
 pub mod handler;
 pub use handler::Response;
 
+pub mod route_table;
+pub use route_table::{RouteTable, RouteInfo, RouteMatch, RouteHandler, Handler, AsyncHandler, HandlerResult, HandlerFuture, RouteFuture, ErrorPage, ErrorPages, Middleware, BeforeHook, AfterHook, BeforeFuture};
+
+pub mod router;
+pub use router::{Router, MethodRouter, get, post, put, delete, patch, options, on};
+
+pub mod startup;
+pub use startup::{run, serve};
+
+pub mod response;
+pub use response::{IntoResponse, Json, Html, RawJson};
+
+pub mod extract;
+pub use extract::{FromRequest, Path, Query, Form, Header, Cookie};
+
+pub mod state;
+pub use state::{AppState, State};
+
+pub mod dev_routes;
+
 pub mod devx;
 pub use devx::{RequestContext, ResponseBuilder, responses};
 
@@ -22,11 +45,14 @@ pub use errors::{FrameworkError, Result};
 pub mod middleware;
 pub use middleware::{ResponseCompressor, QueryParser, CorsMiddleware, RateLimiter};
 
+pub mod route_config;
+pub use route_config::{RouteConfig, RouteKind, Auth, RateLimit};
+
 pub mod request;
 pub use request::{Request, BodyParser};
 
 pub mod auth;
-pub use auth::{Session, SessionStore, JwtAuth, BasicAuth};
+pub use auth::{Session, SessionStore, JwtAuth, BasicAuth, AuthVerifier};
 
 pub mod static_files;
 pub use static_files::{StaticFileServer, StaticFile};
@@ -2,6 +2,13 @@
 use bytes::Bytes;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use once_cell::sync::Lazy;
+use crate::engine::state::AppState;
+
+/// State of requests that weren't handed the app's, shared so `Request::new` doesn't allocate one
+static EMPTY_STATE: Lazy<Arc<AppState>> = Lazy::new(|| Arc::new(AppState::new()));
 
 /// Request body parser
 pub struct BodyParser;
@@ -38,20 +45,42 @@ impl BodyParser {
 #[derive(Debug, Clone)]
 pub struct Request {
     pub method: String,
+    /// Request path without the query string
     pub path: String,
+    /// Decoded query string parameters
+    pub query: HashMap<String, String>,
+    /// Request headers (keys are lowercased)
     pub headers: HashMap<String, String>,
     pub body: Bytes,
+    /// Path parameters extracted by the router
     pub params: HashMap<String, String>,
+    /// Remaining path segments matched by a `[...name]` / `[[...name]]` route
+    /// (the joined path is also available in `params`)
+    pub catch_all: Vec<String>,
+    /// Address of the connected client, when known
+    pub remote_addr: Option<SocketAddr>,
+    /// The app state (`_state.rs`), read by handlers with `State<T>`
+    pub state: Arc<AppState>,
 }
 
 impl Request {
-    pub fn new(method: String, path: String) -> Self {
+    /// Create a request from a method and request target (`/path?query`).
+    /// The query string is split off and decoded into `query`.
+    pub fn new(method: String, target: String) -> Self {
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), crate::engine::middleware::QueryParser::parse(query)),
+            None => (target, HashMap::new()),
+        };
         Self {
             method,
             path,
+            query,
             headers: HashMap::new(),
             body: Bytes::new(),
             params: HashMap::new(),
+            catch_all: Vec::new(),
+            remote_addr: None,
+            state: EMPTY_STATE.clone(),
         }
     }
     
@@ -61,7 +90,7 @@ impl Request {
     }
     
     pub fn with_header(mut self, key: String, value: String) -> Self {
-        self.headers.insert(key, value);
+        self.headers.insert(key.to_ascii_lowercase(), value);
         self
     }
     
@@ -70,6 +99,16 @@ impl Request {
         self
     }
     
+    pub fn with_remote_addr(mut self, addr: SocketAddr) -> Self {
+        self.remote_addr = Some(addr);
+        self
+    }
+    
+    pub fn with_state(mut self, state: Arc<AppState>) -> Self {
+        self.state = state;
+        self
+    }
+    
     /// Parse JSON body
     pub fn json<T: DeserializeOwned>(&self) -> Result<T, String> {
         BodyParser::json(&self.body)
@@ -80,16 +119,19 @@ impl Request {
         BodyParser::form(&self.body)
     }
     
-    /// Get header value
+    /// Get body as string
+    pub fn text(&self) -> Result<String, String> {
+        BodyParser::text(&self.body)
+    }
+    
+    /// Get header value (case-insensitive)
     pub fn header(&self, name: &str) -> Option<&String> {
-        self.headers.get(name)
+        self.headers.get(&name.to_ascii_lowercase())
     }
     
     /// Get query parameter
-    pub fn query(&self, name: &str) -> Option<String> {
-        crate::engine::middleware::QueryParser::extract_from_path(&self.path)
-            .map(|q| crate::engine::middleware::QueryParser::parse(q))
-            .and_then(|map| map.get(name).cloned())
+    pub fn query(&self, name: &str) -> Option<&String> {
+        self.query.get(name)
     }
     
     /// Get route parameter
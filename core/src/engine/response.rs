@@ -0,0 +1,160 @@
+// Conversions from handler return values to a `Response`
+use crate::engine::errors::FrameworkError;
+use crate::engine::handler::Response;
+use bytes::Bytes;
+use serde::Serialize;
+
+/// A value a route handler can return. The generated wrappers call `into_response()` on
+/// whatever the handler returns, so any implementor works as a return type:
+///
+/// ```ignore
+/// pub fn GET(id: u64) -> Option<Json<Order>> { .. }                  // None -> 404
+/// pub fn POST(req: &Request) -> Result<(Json<Post>, u16), FrameworkError> { .. }
+/// pub fn PUT() -> (u16, [(&'static str, &'static str); 1], &'static str) {
+///     (303, [("Location", "/posts")], "")
+/// }
+/// ```
+pub trait IntoResponse {
+    fn into_response(self) -> Response;
+}
+
+/// Serialize `T` as an `application/json` body
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+/// Send `T` as a `text/html` body
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Html<T>(pub T);
+
+/// Send `T`, JSON that is already serialized, as an `application/json` body
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawJson<T>(pub T);
+
+impl IntoResponse for Response {
+    #[inline]
+    fn into_response(self) -> Response {
+        self
+    }
+}
+
+/// Text is always `text/plain`; JSON goes through [`Json`] or [`RawJson`].
+impl IntoResponse for String {
+    fn into_response(self) -> Response {
+        Response { status: 200, body: Bytes::from(self), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
+    }
+}
+
+impl IntoResponse for &'static str {
+    fn into_response(self) -> Response {
+        Response { status: 200, body: Bytes::from_static(self.as_bytes()), content_type: "text/plain; charset=utf-8", headers: Vec::new() }
+    }
+}
+
+impl IntoResponse for Bytes {
+    fn into_response(self) -> Response {
+        Response { status: 200, body: self, content_type: "application/octet-stream", headers: Vec::new() }
+    }
+}
+
+impl IntoResponse for Vec<u8> {
+    fn into_response(self) -> Response {
+        Bytes::from(self).into_response()
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => Response { status: 200, body: Bytes::from(body), content_type: "application/json", headers: Vec::new() },
+            Err(e) => FrameworkError::InternalError(format!("failed to serialize response: {}", e)).to_response(),
+        }
+    }
+}
+
+impl<T: Into<String>> IntoResponse for Html<T> {
+    fn into_response(self) -> Response {
+        Response { status: 200, body: Bytes::from(self.0.into()), content_type: "text/html; charset=utf-8", headers: Vec::new() }
+    }
+}
+
+impl<T: Into<String>> IntoResponse for RawJson<T> {
+    fn into_response(self) -> Response {
+        Response { status: 200, body: Bytes::from(self.0.into()), content_type: "application/json", headers: Vec::new() }
+    }
+}
+
+/// `(body, status)`
+impl<T: IntoResponse> IntoResponse for (T, u16) {
+    fn into_response(self) -> Response {
+        let mut response = self.0.into_response();
+        response.status = self.1;
+        response
+    }
+}
+
+/// `(status, headers, body)`; a `Content-Type` header overrides the body's content type
+impl<H, K, V, T> IntoResponse for (u16, H, T)
+where
+    H: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+    T: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        let (status, headers, body) = self;
+        let mut response = body.into_response();
+        response.status = status;
+        response.headers.extend(headers.into_iter().map(|(name, value)| (name.into(), value.into())));
+        response
+    }
+}
+
+/// `None` is a 404
+impl<T: IntoResponse> IntoResponse for Option<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Some(value) => value.into_response(),
+            None => Response {
+                status: 404,
+                body: Bytes::from_static(b"Not Found"),
+                content_type: "text/plain; charset=utf-8",
+                headers: Vec::new(),
+            },
+        }
+    }
+}
+
+impl<T: IntoResponse, E: IntoResponse> IntoResponse for Result<T, E> {
+    fn into_response(self) -> Response {
+        match self {
+            Ok(value) => value.into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+}
+
+impl IntoResponse for FrameworkError {
+    fn into_response(self) -> Response {
+        self.to_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_response() {
+        let res = (RawJson(r#"{"ok": true}"#.to_string()), 201).into_response();
+        assert_eq!((res.status, res.content_type), (201, "application/json"));
+        assert_eq!("plain".into_response().content_type, "text/plain; charset=utf-8");
+        assert_eq!(r#"{"ok": true}"#.into_response().content_type, "text/plain; charset=utf-8");
+        let res = (303, [("Location", "/posts")], "").into_response();
+        assert_eq!(res.status, 303);
+        assert_eq!(res.headers, vec![("Location".to_string(), "/posts".to_string())]);
+        assert_eq!(None::<Json<u8>>.into_response().status, 404);
+        let err: Result<Json<u8>, FrameworkError> = Err(FrameworkError::ParseError("bad".to_string()));
+        assert_eq!(err.into_response().status, 400);
+        assert_eq!(&Json([1, 2]).into_response().body[..], b"[1,2]");
+    }
+}
@@ -0,0 +1,100 @@
+// Per-route settings declared by a route file with `const ROUTE: RouteConfig = ...`
+use std::time::Duration;
+
+/// What a route serves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteKind {
+    /// Machine-readable responses; listed in the OpenAPI document
+    Api,
+    /// Pages for browsers; left out of the OpenAPI document
+    Ui,
+}
+
+impl RouteKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            RouteKind::Api => "api",
+            RouteKind::Ui => "ui",
+        }
+    }
+}
+
+/// Credentials a request must carry to reach the route, checked by the `AuthVerifier` of the
+/// app state. Missing or rejected credentials are answered with 401.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Auth {
+    None,
+    /// `Authorization: Bearer <token>`
+    Bearer,
+    /// `Authorization: Basic <base64 user:password>`
+    Basic,
+}
+
+/// At most `requests` requests per client address within `per`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub const fn per_second(requests: u32) -> Self {
+        RateLimit { requests, per: Duration::from_secs(1) }
+    }
+
+    pub const fn per_minute(requests: u32) -> Self {
+        RateLimit { requests, per: Duration::from_secs(60) }
+    }
+
+    pub const fn per_hour(requests: u32) -> Self {
+        RateLimit { requests, per: Duration::from_secs(3600) }
+    }
+}
+
+/// Metadata of a route file, checked by build.rs and enforced for every request it handles:
+///
+/// ```ignore
+/// pub const ROUTE: RouteConfig = RouteConfig {
+///     auth: Auth::Bearer,
+///     max_body: Some(64 * 1024),
+///     rate_limit: Some(RateLimit::per_minute(30)),
+///     tags: &["admin"],
+///     ..RouteConfig::DEFAULT
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteConfig {
+    pub kind: RouteKind,
+    /// Missing credentials are answered with 401
+    pub auth: Auth,
+    /// Largest request body in bytes, answered with 413 above it. Can only lower the
+    /// server-wide limit.
+    pub max_body: Option<usize>,
+    /// Async handlers running longer are cancelled and answered with 503
+    pub timeout: Option<Duration>,
+    /// Requests over the limit are answered with 429 and `Retry-After`
+    pub rate_limit: Option<RateLimit>,
+    /// Successful GET responses are reused for this long and sent with `Cache-Control: max-age`
+    pub cache_ttl: Option<Duration>,
+    /// Grouping for docs and tooling (OpenAPI tags, the route manifest)
+    pub tags: &'static [&'static str],
+}
+
+impl RouteConfig {
+    /// Settings of a route file without a `ROUTE` const
+    pub const DEFAULT: RouteConfig = RouteConfig {
+        kind: RouteKind::Api,
+        auth: Auth::None,
+        max_body: None,
+        timeout: None,
+        rate_limit: None,
+        cache_ttl: None,
+        tags: &[],
+    };
+}
+
+impl Default for RouteConfig {
+    fn default() -> Self {
+        RouteConfig::DEFAULT
+    }
+}
@@ -0,0 +1,127 @@
+// The routes of an app, as generated by `rsf_build` and handed to the server at startup
+use crate::engine::errors::FrameworkError;
+use crate::engine::handler::Response;
+use crate::engine::request::Request;
+use crate::engine::route_config::RouteConfig;
+use crate::engine::state::AppState;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A handler's response, or the error an extractor rejected the request with
+pub type HandlerResult = Result<Response, FrameworkError>;
+
+pub type Handler = fn(&Request) -> HandlerResult;
+
+pub type HandlerFuture<'a> = Pin<Box<dyn Future<Output = Response> + Send + 'a>>;
+
+pub type RouteFuture<'a> = Pin<Box<dyn Future<Output = HandlerResult> + Send + 'a>>;
+
+pub type AsyncHandler = for<'a> fn(&'a Request) -> RouteFuture<'a>;
+
+/// A routed handler: sync handlers are called directly, async ones are awaited.
+#[derive(Clone, Copy)]
+pub enum RouteHandler {
+    Sync(Handler),
+    Async(AsyncHandler),
+}
+
+/// Result of a successful route lookup.
+pub struct RouteMatch {
+    pub handler: RouteHandler,
+    pub params: HashMap<String, String>,
+    /// Remaining path segments matched by a `[...name]` / `[[...name]]` segment
+    pub catch_all: Vec<String>,
+    /// `_middleware.rs` hooks wrapping the route, outermost directory first
+    pub middleware: &'static [Middleware],
+    /// Nearest `_404.rs` / `_405.rs` / `_error.rs` of the route
+    pub error_pages: ErrorPages,
+    /// `ROUTE` settings of the route file
+    pub config: &'static RouteConfig,
+    /// Index of the handler in the route table
+    pub id: usize,
+}
+
+/// `fn render(req: &Request, err: &FrameworkError) -> Response` of an error page.
+#[derive(Clone, Copy)]
+pub enum ErrorPage {
+    Sync(fn(&Request, &FrameworkError) -> Response),
+    Async(for<'a> fn(&'a Request, &'a FrameworkError) -> HandlerFuture<'a>),
+}
+
+/// The error pages in scope for a route or path.
+#[derive(Clone, Copy)]
+pub struct ErrorPages {
+    pub not_found: Option<ErrorPage>,
+    pub method_not_allowed: Option<ErrorPage>,
+    pub error: Option<ErrorPage>,
+}
+
+pub type BeforeFuture<'a> = Pin<Box<dyn Future<Output = Option<Response>> + Send + 'a>>;
+
+/// `fn before(req: &Request) -> Option<Response>`: `Some` short-circuits the request.
+#[derive(Clone, Copy)]
+pub enum BeforeHook {
+    Sync(fn(&Request) -> Option<Response>),
+    Async(for<'a> fn(&'a Request) -> BeforeFuture<'a>),
+}
+
+/// `fn after(req: &Request, res: Response) -> Response`
+#[derive(Clone, Copy)]
+pub enum AfterHook {
+    Sync(fn(&Request, Response) -> Response),
+    Async(for<'a> fn(&'a Request, Response) -> HandlerFuture<'a>),
+}
+
+/// The hooks of one `_middleware.rs` file.
+#[derive(Clone, Copy)]
+pub struct Middleware {
+    pub before: Option<BeforeHook>,
+    pub after: Option<AfterHook>,
+}
+
+/// Method, URL pattern and file of a handler in the route table
+#[derive(Debug, Clone, Copy)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    /// e.g. `/users/[id]`
+    pub pattern: &'static str,
+    /// Route file, relative to the app's root, e.g. `routes/users/[id].rs`
+    pub file: &'static str,
+}
+
+/// Everything the build script generates from an app's route trees: the compiled match tree
+/// and handler table, the error pages, the app state and the OpenAPI document. An app gets
+/// its own as `ROUTE_TABLE` from `rsf::routes!()` and hands it to `rsf::run`.
+pub struct RouteTable {
+    /// The handler (with its params, middleware, error pages and settings) for a path + method
+    pub get_handler: fn(&str, &str) -> Option<RouteMatch>,
+    /// Methods that have a route matching a path, used for `Allow` headers
+    pub allowed_methods: fn(&str) -> Vec<&'static str>,
+    /// `ROUTE` settings of the route file with the given pattern, e.g. `/users/[id]`
+    pub route_config: fn(&str) -> Option<&'static RouteConfig>,
+    /// Method, pattern and file of every handler, by route id
+    pub routes: &'static [RouteInfo],
+    /// `ROUTE` settings of every handler, by route id
+    pub configs: &'static [&'static RouteConfig],
+    /// Error pages by URL prefix (`None` = dynamic segment), nearest first; the last entry is the root
+    pub path_error_pages: &'static [(&'static [Option<&'static str>], ErrorPages)],
+    /// The app state built by `_state.rs`, or an empty one
+    pub app_state: fn() -> AppState,
+    /// OpenAPI document for the routes, served at `openapi_path` (`openapi` in project.json)
+    pub openapi_json: &'static str,
+    pub openapi_path: Option<&'static str>,
+    /// Swagger UI page for the document, only served in dev mode
+    pub swagger_ui_path: Option<&'static str>,
+    /// Route manifest (`routes.json`), printed by `rsf::run` for `--routes`
+    pub manifest_json: &'static str,
+}
+
+impl fmt::Debug for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteTable")
+            .field("routes", &self.configs.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,355 @@
+// Routes registered in code, served next to the file-based ones
+use crate::engine::handler::{match_segments, RouteParams};
+use crate::engine::parser::{pattern_segments, ParamConstraint, RouteSegment};
+use crate::engine::route_table::{Handler, RouteHandler, RouteInfo, RouteTable};
+use rsf_pattern::{conflict, route_pattern, validate_pattern, METHODS};
+use std::fmt;
+
+/// Routes that can't be files, e.g. generated from configuration or provided by a library:
+///
+/// ```ignore
+/// let router = Router::new()
+///     .route("/users/:id", get(show_user).delete(delete_user))
+///     .nest("/admin", admin::router());
+/// ```
+///
+/// Patterns take `:name` params and `*name` catch-alls, or the segments of file routes
+/// (`[id:u64]`, `[...slug]`, `[[...slug]]`). At startup the routes are checked against each
+/// other and against the route files with the build's rules, and a path matched by both goes
+/// to the route the match tree tries first: static segments, then typed params, params,
+/// catch-alls and optional catch-alls.
+///
+/// Router routes get the error pages of their path but not the `_middleware.rs` of any route
+/// directory, and run with [`RouteConfig::DEFAULT`](crate::engine::RouteConfig::DEFAULT): no
+/// auth, rate limit, cache, body limit or timeout. Handlers that need them check them themselves.
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+/// A route of a [`Router`]
+#[derive(Clone)]
+pub(crate) struct Route {
+    /// Pattern as registered, e.g. `/users/:id`
+    pub(crate) path: String,
+    /// Pattern in file route syntax, e.g. `/users/[id]`
+    pub(crate) pattern: String,
+    pub(crate) segments: Vec<RouteSegment>,
+    pub(crate) methods: MethodRouter,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router::default()
+    }
+
+    /// Serve `pattern` with the handlers of `methods`, without middleware and with
+    /// `RouteConfig::DEFAULT`
+    pub fn route(mut self, pattern: &str, methods: MethodRouter) -> Self {
+        let segs: Vec<String> = pattern
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|seg| match (seg.strip_prefix(':'), seg.strip_prefix('*')) {
+                (Some(name), _) => format!("[{}]", name),
+                (_, Some(name)) => format!("[...{}]", name),
+                _ => seg.to_string(),
+            })
+            .collect();
+        let normalized = format!("/{}", segs.join("/"));
+        self.routes.push(Route {
+            path: pattern.to_string(),
+            segments: pattern_segments(&normalized),
+            pattern: normalized,
+            methods,
+        });
+        self
+    }
+
+    /// Serve every route of `router` under `prefix`
+    pub fn nest(mut self, prefix: &str, router: Router) -> Self {
+        let prefix = prefix.trim_end_matches('/');
+        for route in router.routes {
+            self = self.route(&format!("{}{}", prefix, route.path), route.methods);
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub(crate) fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// The route for a path + method, with its index and params. Routes of a router can't
+    /// overlap (see [`Router::check`]) except for params with different constraints, which
+    /// are tried in the order they were added.
+    pub(crate) fn find(&self, path: &str, method: &str) -> Option<(usize, &Route, RouteHandler, RouteParams)> {
+        let mut best: Option<(usize, &Route, RouteHandler, RouteParams)> = None;
+        for (i, route) in self.routes.iter().enumerate() {
+            let Some(handler) = route.methods.handler(method) else { continue };
+            if best.as_ref().is_some_and(|(_, b, _, _)| !precedes(&route.segments, &b.segments)) {
+                continue;
+            }
+            if let Some(params) = match_segments(&route.segments, path) {
+                best = Some((i, route, handler, params));
+            }
+        }
+        best
+    }
+
+    /// Methods of the routes matching a path
+    pub(crate) fn allowed_methods(&self, path: &str) -> Vec<&'static str> {
+        let mut methods: Vec<&'static str> = Vec::new();
+        for route in &self.routes {
+            if match_segments(&route.segments, path).is_some() {
+                for method in route.methods.methods() {
+                    if !methods.contains(&method) {
+                        methods.push(method);
+                    }
+                }
+            }
+        }
+        methods
+    }
+
+    /// Errors that keep the routes from being served next to the route files of `table`:
+    /// malformed patterns, unknown or repeated methods, and routes that are duplicate,
+    /// ambiguous or overlapping by the rules `rsf_build` applies to route files.
+    pub fn check(&self, table: &RouteTable) -> Vec<String> {
+        let mut errors = Vec::new();
+        for route in &self.routes {
+            for err in validate_pattern(&route_pattern(&route.pattern)) {
+                errors.push(format!("{}: {}", route.path, err));
+            }
+            for (i, (method, _)) in route.methods.handlers.iter().enumerate() {
+                if !METHODS.contains(method) {
+                    errors.push(format!("{}: unsupported method `{}` (expected one of {})", route.path, method, METHODS.join(", ")));
+                } else if route.methods.handlers[..i].iter().any(|(m, _)| m == method) {
+                    errors.push(format!("{}: more than one {} handler", route.path, method));
+                }
+            }
+        }
+        // Conflicts are only meaningful between well-formed patterns
+        if !errors.is_empty() {
+            return errors;
+        }
+
+        let mut files: Vec<&RouteInfo> = Vec::new();
+        for info in table.routes {
+            if !files.iter().any(|f| f.pattern == info.pattern) {
+                files.push(info);
+            }
+        }
+        for (i, a) in self.routes.iter().enumerate() {
+            for b in &self.routes[i + 1..] {
+                if let Some(reason) = conflict(&a.pattern, &b.pattern) {
+                    errors.push(format!("{}:\n    Router: {}\n    Router: {}", reason, a.path, b.path));
+                }
+            }
+            for file in &files {
+                if let Some(reason) = conflict(&a.pattern, file.pattern) {
+                    errors.push(format!("{}:\n    Router: {}\n    {}", reason, a.path, file.file));
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.routes.iter().map(|r| &r.path)).finish()
+    }
+}
+
+/// Handlers of a [`Router`] route by method: `get(list).post(create)`
+#[derive(Clone, Default)]
+pub struct MethodRouter {
+    handlers: Vec<(&'static str, RouteHandler)>,
+}
+
+impl MethodRouter {
+    pub fn get(self, handler: Handler) -> Self {
+        self.on("GET", RouteHandler::Sync(handler))
+    }
+
+    pub fn post(self, handler: Handler) -> Self {
+        self.on("POST", RouteHandler::Sync(handler))
+    }
+
+    pub fn put(self, handler: Handler) -> Self {
+        self.on("PUT", RouteHandler::Sync(handler))
+    }
+
+    pub fn delete(self, handler: Handler) -> Self {
+        self.on("DELETE", RouteHandler::Sync(handler))
+    }
+
+    pub fn patch(self, handler: Handler) -> Self {
+        self.on("PATCH", RouteHandler::Sync(handler))
+    }
+
+    pub fn options(self, handler: Handler) -> Self {
+        self.on("OPTIONS", RouteHandler::Sync(handler))
+    }
+
+    /// Handle `method` (upper case) with a sync or async handler
+    pub fn on(mut self, method: &'static str, handler: RouteHandler) -> Self {
+        self.handlers.push((method, handler));
+        self
+    }
+
+    pub(crate) fn handler(&self, method: &str) -> Option<RouteHandler> {
+        self.handlers.iter().find(|(m, _)| *m == method).map(|(_, h)| *h)
+    }
+
+    /// Methods with a handler, in the order they were added
+    pub(crate) fn methods(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.handlers.iter().map(|(m, _)| *m)
+    }
+}
+
+pub fn get(handler: Handler) -> MethodRouter {
+    MethodRouter::default().get(handler)
+}
+
+pub fn post(handler: Handler) -> MethodRouter {
+    MethodRouter::default().post(handler)
+}
+
+pub fn put(handler: Handler) -> MethodRouter {
+    MethodRouter::default().put(handler)
+}
+
+pub fn delete(handler: Handler) -> MethodRouter {
+    MethodRouter::default().delete(handler)
+}
+
+pub fn patch(handler: Handler) -> MethodRouter {
+    MethodRouter::default().patch(handler)
+}
+
+pub fn options(handler: Handler) -> MethodRouter {
+    MethodRouter::default().options(handler)
+}
+
+/// `on("GET", RouteHandler::Async(handler))`, for async handlers and other methods
+pub fn on(method: &'static str, handler: RouteHandler) -> MethodRouter {
+    MethodRouter::default().on(method, handler)
+}
+
+/// Whether a route with segments `a` is tried before one with segments `b`, as in the
+/// compiled match tree: segment by segment, static before typed param before regex param
+/// before plain param before catch-all before optional catch-all. Ties keep the earlier route.
+pub(crate) fn precedes(a: &[RouteSegment], b: &[RouteSegment]) -> bool {
+    fn rank(seg: &RouteSegment) -> u8 {
+        match seg {
+            RouteSegment::Static(_) => 0,
+            RouteSegment::Dynamic(_, Some(ParamConstraint::Type(_))) => 1,
+            RouteSegment::Dynamic(_, Some(ParamConstraint::Regex(_))) => 2,
+            RouteSegment::Dynamic(_, None) => 3,
+            RouteSegment::CatchAll(_) => 4,
+            RouteSegment::OptionalCatchAll(_) => 5,
+        }
+    }
+    a.iter().map(rank).lt(b.iter().map(rank))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::handler::Response;
+    use crate::engine::request::Request;
+    use crate::engine::route_table::HandlerResult;
+    use crate::engine::state::AppState;
+
+    fn ok(_req: &Request) -> HandlerResult {
+        Ok(Response { status: 200, body: Default::default(), content_type: "text/plain", headers: Vec::new() })
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route("/users/:id", get(ok).delete(ok))
+            .nest("/users", Router::new().route("/me", get(ok)))
+            .route("/files/*path", post(ok))
+    }
+
+    #[test]
+    fn test_route_syntax() {
+        let router = router();
+        let patterns: Vec<&str> = router.routes().iter().map(|r| r.pattern.as_str()).collect();
+        assert_eq!(patterns, ["/users/[id]", "/users/me", "/files/[...path]"]);
+        assert_eq!(router.routes()[1].path, "/users/me");
+    }
+
+    #[test]
+    fn test_static_segments_win() {
+        let router = router();
+        let (index, route, _, params) = router.find("/users/me", "GET").unwrap();
+        assert_eq!((index, route.pattern.as_str()), (1, "/users/me"));
+        assert!(params.is_empty());
+        // `/users/me` has no DELETE handler, so the param route serves it
+        let (_, route, _, params) = router.find("/users/me", "DELETE").unwrap();
+        assert_eq!(route.path, "/users/:id");
+        assert_eq!(params[0], ("id".to_string(), "me".to_string()));
+    }
+
+    #[test]
+    fn test_catch_all_params() {
+        let router = router();
+        assert_eq!(router.find("/files/a/b", "POST").unwrap().3[0], ("path".to_string(), "a/b".to_string()));
+        assert!(router.find("/files", "POST").is_none());
+    }
+
+    #[test]
+    fn test_allowed_methods() {
+        let router = router();
+        assert_eq!(router.allowed_methods("/users/7"), ["GET", "DELETE"]);
+        assert_eq!(router.allowed_methods("/users/me"), ["GET", "DELETE"]);
+        assert!(router.allowed_methods("/posts").is_empty());
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert!(precedes(&pattern_segments("/orders/[id:u64]"), &pattern_segments("/orders/[code]")));
+        assert!(precedes(&pattern_segments("/orders/[id:u64]"), &pattern_segments("/orders/[code:regex([A-Z]+)]")));
+        assert!(!precedes(&pattern_segments("/orders/[code:regex([A-Z]+)]"), &pattern_segments("/orders/[id:u64]")));
+        assert!(precedes(&pattern_segments("/orders/[code:regex([A-Z]+)]"), &pattern_segments("/orders/[code]")));
+        assert!(precedes(&pattern_segments("/docs/[page]/edit"), &pattern_segments("/docs/[...slug]")));
+        assert!(!precedes(&pattern_segments("/docs/[...slug]"), &pattern_segments("/docs/[page]/edit")));
+    }
+
+    #[test]
+    fn test_check() {
+        static TABLE: RouteTable = RouteTable {
+            get_handler: |_, _| None,
+            allowed_methods: |_| Vec::new(),
+            route_config: |_| None,
+            routes: &[RouteInfo { method: "GET", pattern: "/posts/[slug]", file: "routes/posts/[slug].rs" }],
+            configs: &[],
+            path_error_pages: &[],
+            app_state: AppState::new,
+            openapi_json: "",
+            openapi_path: None,
+            swagger_ui_path: None,
+            manifest_json: "",
+        };
+        let table = &TABLE;
+        assert!(router().check(table).is_empty());
+        let errors = Router::new()
+            .route("/files/*path/raw", get(ok))
+            .route("/a/:id", MethodRouter::default().get(ok).get(ok).on("TRACE", RouteHandler::Sync(ok)))
+            .check(table);
+        assert_eq!(errors, [
+            "/files/*path/raw: catch-all segment must be the last segment",
+            "/a/:id: more than one GET handler",
+            "/a/:id: unsupported method `TRACE` (expected one of GET, POST, PUT, DELETE, PATCH, OPTIONS, HEAD)",
+        ]);
+        let errors = Router::new().route("/users/:id", get(ok)).route("/users/:name", post(ok)).check(table);
+        assert_eq!(errors, ["ambiguous routes /users/[id] and /users/[name]:\n    Router: /users/:id\n    Router: /users/:name"]);
+        let errors = Router::new().route("/posts/:id", get(ok)).check(table);
+        assert_eq!(errors, ["ambiguous routes /posts/[id] and /posts/[slug]:\n    Router: /posts/:id\n    routes/posts/[slug].rs"]);
+    }
+}
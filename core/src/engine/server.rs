@@ -2,9 +2,12 @@
 // import the current Runtime
 
 use crate::engine::runtime::Runtime;
+use crate::engine::route_table::RouteTable;
 use crate::engine::handler::RequestHandler;
-use tokio::net::TcpListener;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use crate::engine::router::Router;
+
+/// Largest request body the server will buffer (10 MiB)
+pub(crate) const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 
 // If the `http` crate isn't a dependency, provide a simple local HttpServer placeholder.
 // Replace or remove this when integrating a real HTTP server implementation.
@@ -26,13 +29,19 @@ pub struct Server {
 }
 
 impl Server {
-    pub fn new(port: u16, host: String, dev: bool) -> Self {
-        let runtime = Runtime::new(port, host.clone(), dev);
+    pub fn new(routes: &'static RouteTable, port: u16, host: String, dev: bool) -> Self {
+        let runtime = Runtime::new(routes, port, host.clone(), dev);
         let http_server = HttpServer::new(port, &host);
         let handler = RequestHandler::new(&runtime);
         Server { runtime, http_server, handler }
     }
 
+    /// Serve the routes of `router` next to the route files, unless they conflict
+    pub fn with_router(mut self, router: Router) -> Result<Self, Vec<String>> {
+        self.handler = self.handler.with_router(router)?;
+        Ok(self)
+    }
+
     pub async fn start(self) {
     // Placeholder start function
     log::info!("Starting server at {}:{}", self.http_server.host, self.http_server.port);
@@ -50,9 +59,15 @@ impl Server {
 
         #[cfg(not(feature = "use_hyper"))]
         {
+            use crate::engine::errors::FrameworkError;
+            use crate::engine::request::Request;
+            use bytes::Bytes;
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            use tokio::net::TcpListener;
+
             let listener = TcpListener::bind(&addr).await.unwrap();
             loop {
-                let (mut socket, _) = listener.accept().await.unwrap();
+                let (mut socket, peer_addr) = listener.accept().await.unwrap();
                 let handler = handler.clone();
                 tokio::spawn(async move {
                     use tokio::time::{timeout, Duration};
@@ -82,8 +97,16 @@ impl Server {
                         return;
                     }
 
-                    let req = String::from_utf8_lossy(&buf);
-                    let mut lines = req.lines();
+                    // Split the buffer into the header block and whatever part of the body was already read
+                    let head_end = match buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        Some(pos) => pos + 4,
+                        None => {
+                            let _ = socket.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n").await;
+                            return;
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+                    let mut lines = head.lines();
                     let request_line = match lines.next() {
                         Some(l) => l,
                         None => return,
@@ -93,22 +116,57 @@ impl Server {
                         let _ = socket.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n").await;
                         return;
                     }
-                    let method = parts[0];
-                    let path = parts[1];
+                    let mut request = Request::new(parts[0].to_string(), parts[1].to_string())
+                        .with_remote_addr(peer_addr);
+                    for line in lines {
+                        if let Some((name, value)) = line.split_once(':') {
+                            request = request.with_header(name.trim().to_string(), value.trim().to_string());
+                        }
+                    }
 
-                        let response = handler.handle_request(method, path).await;
+                    // Read the rest of the body as announced by Content-Length
+                    let content_length = request.header("content-length")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    // HEAD responses carry the GET headers and Content-Length, but no body
+                    let is_head = request.method.eq_ignore_ascii_case("HEAD");
+                    let response = if content_length > MAX_BODY_SIZE {
+                        handler.render_error(&request, FrameworkError::PayloadTooLarge { limit: MAX_BODY_SIZE }).await
+                    } else {
+                        let mut body = buf.split_off(head_end);
+                        let read_body = timeout(Duration::from_secs(30), async {
+                            while body.len() < content_length {
+                                let n = socket.read(&mut tmp).await.map_err(|e| e.to_string())?;
+                                if n == 0 { break; }
+                                body.extend_from_slice(&tmp[..n]);
+                            }
+                            Ok::<(), String>(())
+                        }).await;
+                        if !matches!(read_body, Ok(Ok(()))) || body.len() < content_length {
+                            let err = FrameworkError::ParseError("incomplete request body".to_string());
+                            handler.render_error(&request, err).await
+                        } else {
+                            body.truncate(content_length);
+                            handler.handle_request(request.with_body(Bytes::from(body))).await
+                        }
+                    };
                         // build response head
-                        let mut head = format!("HTTP/1.1 {} {}\r\nContent-Length: {}\r\nContent-Type: {}\r\n",
+                        let mut head = format!("HTTP/1.1 {} {}\r\nContent-Length: {}\r\n",
                             response.status,
                             crate::engine::handler::status_text(response.status),
-                            response.body.len(),
-                            response.content_type);
+                            response.body.len());
+                        // A `Content-Type` header set by the handler wins over `content_type`
+                        if !response.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-type")) {
+                            head.push_str(&format!("Content-Type: {}\r\n", response.content_type));
+                        }
                         for (k, v) in &response.headers {
                             head.push_str(&format!("{}: {}\r\n", k, v));
                         }
                         head.push_str("Connection: close\r\n\r\n");
                         let _ = socket.write_all(head.as_bytes()).await;
-                        let _ = socket.write_all(&response.body).await;
+                        if !is_head {
+                            let _ = socket.write_all(&response.body).await;
+                        }
                 });
             }
         }
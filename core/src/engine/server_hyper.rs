@@ -1,27 +1,50 @@
+// Hyper integration: serves the RequestHandler behind a hyper server
 use std::convert::Infallible;
 use std::sync::Arc;
-use hyper::{Server, service::{make_service_fn, service_fn}, Body, Request as HyperRequest, Response as HyperResponse, Method};
-use crate::engine::handler::{RequestHandler, Response as MyResponse};
+use hyper::body::HttpBody;
+use hyper::{Server, server::conn::AddrStream, service::{make_service_fn, service_fn}, Body, Request as HyperRequest, Response as HyperResponse};
+use crate::engine::handler::RequestHandler;
+use crate::engine::request::Request;
+use crate::engine::errors::FrameworkError;
+use crate::engine::server::MAX_BODY_SIZE;
+use bytes::{Bytes, BytesMut};
 
-// Adapter: converts hyper requests to our RequestHandler and builds hyper responses.
+/// Serve `handler` on `addr` with hyper
 pub async fn run_hyper(handler: Arc<RequestHandler>, addr: std::net::SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let make_svc = make_service_fn(move |_| {
+    let make_svc = make_service_fn(move |conn: &AddrStream| {
         let handler = handler.clone();
+        let remote_addr = conn.remote_addr();
         async move {
             Ok::<_, Infallible>(service_fn(move |req: HyperRequest<Body>| {
                 let handler = handler.clone();
                 async move {
-                    let method = req.method().as_str().to_string();
-                    let path = req.uri().path().to_string();
-                    // For simplicity, ignore body for now
-                    let my_resp = handler.handle_request(&method, &path).await;
+                    let (parts, body) = req.into_parts();
+                    let target = parts.uri.path_and_query()
+                        .map(|pq| pq.as_str().to_string())
+                        .unwrap_or_else(|| parts.uri.path().to_string());
+                    let mut request = Request::new(parts.method.as_str().to_string(), target)
+                        .with_remote_addr(remote_addr);
+                    for (name, value) in parts.headers.iter() {
+                        if let Ok(value) = value.to_str() {
+                            request = request.with_header(name.as_str().to_string(), value.to_string());
+                        }
+                    }
+                    let my_resp = match read_body(body).await {
+                        Ok(bytes) => handler.handle_request(request.with_body(bytes)).await,
+                        Err(err) => handler.render_error(&request, err).await,
+                    };
                     // Build a proper Hyper response using the structured Response returned by handler
                     let mut builder = HyperResponse::builder()
                         .status(my_resp.status);
-                    // set content-type and content-length
+                    for (k, v) in &my_resp.headers {
+                        builder = builder.header(k.as_str(), v.as_str());
+                    }
+                    // set content-type (unless the handler set the header) and content-length
+                    if !my_resp.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-type")) {
+                        builder = builder.header("content-type", my_resp.content_type);
+                    }
                     let body = Body::from(my_resp.body);
                     let resp = builder
-                        .header("content-type", my_resp.content_type)
                         .header("content-length", body.size_hint().lower().to_string())
                         .body(body)
                         .unwrap_or_else(|_| HyperResponse::new(Body::from("Internal Server Error")));
@@ -35,3 +58,21 @@ pub async fn run_hyper(handler: Arc<RequestHandler>, addr: std::net::SocketAddr)
     server.await?;
     Ok(())
 }
+
+/// Buffer a request body of at most `MAX_BODY_SIZE` bytes; a larger one is a 413, whether its
+/// `Content-Length` announces it or it only turns out larger while streaming
+async fn read_body(mut body: Body) -> Result<Bytes, FrameworkError> {
+    let too_large = FrameworkError::PayloadTooLarge { limit: MAX_BODY_SIZE };
+    if body.size_hint().lower() > MAX_BODY_SIZE as u64 {
+        return Err(too_large);
+    }
+    let mut bytes = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| FrameworkError::ParseError(e.to_string()))?;
+        if bytes.len() + chunk.len() > MAX_BODY_SIZE {
+            return Err(too_large);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.freeze())
+}
@@ -0,0 +1,122 @@
+// Entry point of an app: logging, the startup banner and the server
+use crate::engine::dev_routes::DEV_ROUTES_PATH;
+use crate::engine::hot_reload::HotReloader;
+use crate::engine::route_config::RouteKind;
+use crate::engine::route_table::{RouteInfo, RouteTable};
+use crate::engine::router::Router;
+use crate::engine::server::Server;
+use colored::Colorize;
+
+/// Serve the app's routes, and those of `router`, on a new tokio runtime until the process is
+/// stopped. This is the `main` of `rsf::main!()`; an app with a `main` of its own calls it with
+/// the `ROUTE_TABLE` of `rsf::routes!()`, or awaits `serve` from a runtime it already has.
+/// Run with `--routes`, it prints the route manifest instead (what `cli routes` reads).
+pub fn run(routes: &'static RouteTable, router: Router) {
+    if std::env::args().skip(1).any(|arg| arg == "--routes") {
+        print!("{}", routes.manifest_json);
+        return;
+    }
+
+    // Initialize structured logging from environment (RUST_LOG). Default to info.
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format_timestamp_millis()
+        .init();
+
+    tokio::runtime::Runtime::new()
+        .expect("Failed to start the tokio runtime")
+        .block_on(serve(routes, router));
+}
+
+/// Print the startup banner and serve the app's routes and those of `router`, with hot reload
+/// in dev mode. Exits if `router` conflicts with the route files.
+pub async fn serve(routes: &'static RouteTable, router: Router) {
+    println!("\n{}", "🚀 Framework Starting...".bright_cyan().bold());
+    println!("{}", "═".repeat(50).bright_blue());
+
+    // One line per route file of the generated table; its handlers share the file's settings
+    let mut route_files: Vec<(usize, &RouteInfo)> = Vec::new();
+    for (id, info) in routes.routes.iter().enumerate() {
+        if !route_files.iter().any(|(_, f)| f.file == info.file) {
+            route_files.push((id, info));
+        }
+    }
+
+    if route_files.is_empty() {
+        log::warn!("{} No route files were compiled in!", "⚠️".bright_yellow());
+    } else {
+        println!("\n{} Found {} route file(s):",
+            "📁".bright_green(),
+            route_files.len().to_string().bright_white().bold()
+        );
+
+        for (idx, (id, info)) in route_files.iter().enumerate() {
+            let route_type = match routes.configs[*id].kind {
+                RouteKind::Api => "API".bright_blue(),
+                RouteKind::Ui => "UI".bright_magenta(),
+            };
+            println!("  {}. [{}] {} {}",
+                (idx + 1).to_string().bright_white(),
+                route_type,
+                info.pattern.bright_white(),
+                info.file.bright_cyan()
+            );
+        }
+    }
+
+    // Start server for manual testing
+    let port = 8080;
+    let host = "127.0.0.1".to_string();
+    let dev_mode = std::env::var("DEV").is_ok() || cfg!(debug_assertions);
+
+    println!("\n{}", "═".repeat(50).bright_blue());
+    println!("{} Server Configuration:", "⚙️".bright_green());
+    println!("  • Address: {}:{}", host.bright_white(), port.to_string().bright_white().bold());
+    println!("  • Mode: {}", if dev_mode {
+        "Development".bright_yellow().bold()
+    } else {
+        "Production".bright_green().bold()
+    });
+    println!("  • Hot Reload: {}", if dev_mode {
+        "Enabled ♻️".bright_green()
+    } else {
+        "Disabled".bright_red()
+    });
+    if dev_mode {
+        println!("  • Route Explorer: {}", format!("http://{}:{}{}", host, port, DEV_ROUTES_PATH).bright_cyan());
+    }
+    println!("{}", "═".repeat(50).bright_blue());
+
+    // Enable hot reload in dev mode
+    if dev_mode && let Ok(watch_path) = std::env::current_dir() {
+        let hot_reloader = HotReloader::new(watch_path.to_string_lossy().to_string());
+        if let Err(e) = hot_reloader.start() {
+            log::warn!("{} Hot reload failed to start: {}", "⚠️".bright_yellow(), e);
+        }
+    }
+
+    let router_routes = router.routes().len();
+    let server = match Server::new(routes, port, host.clone(), dev_mode).with_router(router) {
+        Ok(server) => server,
+        Err(errors) => {
+            for err in &errors {
+                eprintln!("{} {}", "error:".bright_red().bold(), err);
+            }
+            eprintln!("{} {} route error(s) found in the Router, aborting startup", "error:".bright_red().bold(), errors.len());
+            std::process::exit(1);
+        }
+    };
+    if router_routes > 0 {
+        println!("{} {} route(s) registered with the Router", "🧭".bright_green(), router_routes.to_string().bright_white().bold());
+    }
+
+    println!("\n{} Server listening on {}http://{}:{}{}",
+        "✓".bright_green().bold(),
+        "".bright_white(),
+        host.bright_cyan(),
+        port.to_string().bright_cyan().bold(),
+        "".bright_white()
+    );
+    println!("{} Press Ctrl+C to stop\n", "ℹ".bright_blue());
+
+    server.start().await;
+}
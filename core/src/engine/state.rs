@@ -0,0 +1,100 @@
+// Application state shared by every handler, registered by type
+use crate::engine::errors::FrameworkError;
+use crate::engine::extract::FromRequest;
+use crate::engine::request::Request;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Values built once at startup (a DB pool, the `SessionStore`, a `JwtAuth`, `WsRoom`s, ...)
+/// and handed to every request, one per type. The app's is returned by `fn state()` in the
+/// `_state.rs` at the root of a route tree:
+///
+/// ```ignore
+/// pub fn state() -> AppState {
+///     AppState::new()
+///         .with(JwtAuth::new(std::env::var("JWT_SECRET").unwrap_or_default()))
+///         .with(SessionStore::new(Duration::from_secs(3600)))
+/// }
+/// ```
+///
+/// Handlers read a value with the `State<T>` extractor; tests can give a request or a
+/// `RequestHandler` a state of their own with `with_state`.
+#[derive(Clone, Default)]
+pub struct AppState {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `value`, replacing the value of the same type
+    pub fn with<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Register `value` and return the value of the same type it replaces
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<Arc<T>> {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value)).and_then(|old| old.downcast().ok())
+    }
+
+    /// The registered value of type `T`
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.values.get(&TypeId::of::<T>()).and_then(|value| value.clone().downcast().ok())
+    }
+}
+
+impl fmt::Debug for AppState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppState").field("values", &self.values.len()).finish()
+    }
+}
+
+/// A value of the app state: `State(sessions): State<SessionStore>`. A type that was never
+/// registered is a 500.
+#[derive(Debug)]
+pub struct State<T>(pub Arc<T>);
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        State(self.0.clone())
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> FromRequest for State<T> {
+    fn from_request(req: &Request, _name: &str) -> Result<Self, FrameworkError> {
+        req.state.get::<T>().map(State).ok_or_else(|| {
+            FrameworkError::InternalError(format!("no `{}` in the app state", std::any::type_name::<T>()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_state() {
+        let mut state = AppState::new().with(String::from("primary")).with(7u32);
+        assert_eq!(state.insert(String::from("test double")).as_deref().map(String::as_str), Some("primary"));
+        let req = Request::new("GET".to_string(), "/".to_string()).with_state(Arc::new(state));
+        let State(name) = State::<String>::from_request(&req, "").unwrap();
+        assert_eq!(name.as_str(), "test double");
+        assert_eq!(*State::<u32>::from_request(&req, "").unwrap(), 7);
+        let missing = State::<u64>::from_request(&req, "").map_err(|err| err.status_code());
+        assert_eq!(missing.err(), Some(500));
+    }
+}
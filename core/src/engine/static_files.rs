@@ -42,14 +42,12 @@ impl StaticFileServer {
         }
         
         // Check cache first
-        if self.cache_enabled {
-            if let Some(cached) = self.cache.get(path) {
-                return Ok(StaticFile {
-                    content: cached.clone(),
-                    content_type: Self::guess_content_type(&full_path),
-                    etag: None,
-                });
-            }
+        if self.cache_enabled && let Some(cached) = self.cache.get(path) {
+            return Ok(StaticFile {
+                content: cached.clone(),
+                content_type: Self::guess_content_type(&full_path),
+                etag: None,
+            });
         }
         
         // Read file
@@ -0,0 +1,102 @@
+//! File-based routing for Rust web apps: every `.rs` file of an app's route directory is a
+//! route (`users/[id].rs` → `/users/:id`), compiled into a match tree by the app's build
+//! script. An app depends on `rsf`, and on `rsf-build` for its `build.rs`:
+//!
+//! ```toml
+//! [dependencies]
+//! rsf = "0.1"
+//!
+//! [build-dependencies]
+//! rsf-build = "0.1"
+//! ```
+//!
+//! `build.rs` generates the routes of the route directory:
+//!
+//! ```ignore
+//! fn main() {
+//!     rsf_build::generate("routes");
+//! }
+//! ```
+//!
+//! and `src/main.rs` serves them:
+//!
+//! ```ignore
+//! rsf::main!();
+//! ```
+//!
+//! Routes that can't be files go in a [`Router`], served next to them:
+//!
+//! ```ignore
+//! rsf::main!(router);
+//!
+//! fn router() -> rsf::Router {
+//!     rsf::Router::new().route("/plugins/:name", rsf::get(plugins::show))
+//! }
+//! ```
+//!
+//! Route files import what they need from the crate root (`use rsf::{Json, Request}`); the
+//! [`engine`] modules hold the rest.
+
+pub mod engine;
+
+pub use engine::{
+    run, serve, RouteTable, Router, MethodRouter, get, post, put, delete, patch, options, on,
+    Request, BodyParser, Response, IntoResponse, Json, Html, RawJson, ResponseBuilder, RequestContext, responses,
+    FromRequest, Path, Query, Form, Header, Cookie, AppState, State,
+    FrameworkError, Result,
+    RouteConfig, RouteKind, Auth, RateLimit,
+    Session, SessionStore, JwtAuth, BasicAuth, AuthVerifier,
+    ResponseCompressor, QueryParser, CorsMiddleware, RateLimiter,
+    StaticFileServer, StaticFile,
+    WebSocket, WsMessage, WsRoom, WsHandler,
+};
+
+/// Include the code `rsf_build::generate` wrote for the app: `ROUTE_TABLE`, to hand to
+/// [`run`] with a [`Router`], the `routes` URL builders and the `crate::app` modules of the `_lib/` folders.
+/// For apps with a `main` of their own; [`main!`] calls it for the others.
+#[macro_export]
+macro_rules! routes {
+    () => {
+        /// Shared modules of the route trees' `_lib/` folders: `use crate::app::models::User`
+        mod app {
+            include!(concat!(env!("OUT_DIR"), "/generated_app.rs"));
+        }
+
+        /// Route wrappers, match tree and handler table generated from the route files
+        mod generated {
+            include!(concat!(env!("OUT_DIR"), "/generated_routes.rs"));
+        }
+        #[allow(unused_imports)]
+        pub use generated::{routes, ROUTE_TABLE};
+    };
+}
+
+/// The whole `src/main.rs` of an app: its [`routes!`], jemalloc as the global allocator and a
+/// `main` that [`run`]s the server. `rsf::main!(router)` also serves the [`Router`] returned by
+/// `fn router() -> Router`.
+#[macro_export]
+macro_rules! main {
+    () => {
+        $crate::main!($crate::Router::new);
+    };
+    ($router:path) => {
+        $crate::routes!();
+
+        #[cfg(not(target_env = "msvc"))]
+        #[global_allocator]
+        static GLOBAL: $crate::__private::Jemalloc = $crate::__private::Jemalloc;
+
+        fn main() {
+            $crate::run(&ROUTE_TABLE, $router());
+        }
+    };
+}
+
+/// Crates the generated code refers to, so apps don't have to depend on them
+#[doc(hidden)]
+pub mod __private {
+    pub use percent_encoding;
+    pub use regex;
+    #[cfg(not(target_env = "msvc"))]
+    pub use tikv_jemallocator::Jemalloc;
+}
@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Benchmark the real match tree generated by build.rs for the routes of the example app
+#[allow(non_snake_case, dead_code)]
+mod router {
+    include!(concat!(env!("OUT_DIR"), "/generated_router.rs"));
+}
+
+fn bench_route_matching(c: &mut Criterion) {
+    c.bench_function("route_match_static", |b| {
+        b.iter(|| {
+            // /api is a static route: no params, no allocation
+            let route = black_box("/api");
+            let id = router::match_route(black_box("GET"), route);
+            black_box(id.map(|id| router::route_params(id, route)))
+        })
+    });
+
+    c.bench_function("route_match_dynamic", |b| {
+        b.iter(|| {
+            let route = black_box("/users/123");
+            let id = router::match_route(black_box("GET"), route);
+            black_box(id.map(|id| router::route_params(id, route)))
+        })
+    });
+
+    c.bench_function("route_match_nested", |b| {
+        b.iter(|| {
+            let route = black_box("/posts/42/comments/7");
+            let id = router::match_route(black_box("GET"), route);
+            black_box(id.map(|id| router::route_params(id, route)))
+        })
+    });
+
+    c.bench_function("route_match_catch_all", |b| {
+        b.iter(|| {
+            let route = black_box("/docs/guides/routing/advanced");
+            let id = router::match_route(black_box("GET"), route);
+            black_box(id.map(|id| router::route_params(id, route)))
+        })
+    });
+
+    c.bench_function("route_match_miss", |b| {
+        b.iter(|| black_box(router::match_route(black_box("GET"), black_box("/does/not/exist"))))
+    });
+}
+
+criterion_group!(benches, bench_route_matching);
+criterion_main!(benches);
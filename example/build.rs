@@ -0,0 +1,3 @@
+fn main() {
+    rsf_build::generate("routes");
+}
@@ -0,0 +1,8 @@
+// Route group example: "(marketing)" organises files without changing the URL -> /pricing
+use rsf::{Html, RouteConfig, RouteKind};
+
+pub const ROUTE: RouteConfig = RouteConfig { kind: RouteKind::Ui, ..RouteConfig::DEFAULT };
+
+pub fn GET() -> Html<&'static str> {
+    Html("<h1>Pricing</h1><ul><li>Free: $0</li><li>Pro: $19/month</li></ul>")
+}
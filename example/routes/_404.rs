@@ -0,0 +1,13 @@
+// Not-found page for every path without a closer _404.rs
+use rsf::{FrameworkError, Request, Response, ResponseBuilder};
+
+pub fn render(req: &Request, _err: &FrameworkError) -> Response {
+    ResponseBuilder::new()
+        .status(404)
+        .html(format!(
+            "<!doctype html><html><head><title>Not found</title></head>\
+             <body><h1>404</h1><p>Nothing lives at <code>{}</code>.</p><a href=\"/\">Back home</a></body></html>",
+            req.path.replace('<', "&lt;").replace('>', "&gt;")
+        ))
+        .build()
+}
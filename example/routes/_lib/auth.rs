@@ -0,0 +1,15 @@
+// ✅ REAL EXAMPLE: Shared module (`crate::app::auth`)
+// Files in `_lib/` are compiled once and never become routes
+use rsf::Request;
+
+/// One secret for every route that issues or checks tokens (see `_state.rs`)
+pub fn jwt_secret() -> String {
+    std::env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key-change-in-production".to_string())
+}
+
+/// The token of an `Authorization: Bearer <token>` header, or ""
+pub fn bearer_token(req: &Request) -> &str {
+    req.header("authorization")
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or("")
+}
@@ -0,0 +1,24 @@
+// ✅ REAL EXAMPLE: Shared models (`crate::app::models`)
+use serde::Serialize;
+
+/// A user as listed by `/api/admin/users`
+#[derive(Serialize)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub created_at: String,
+}
+
+/// Error body of the auth and admin routes
+#[derive(Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl ErrorResponse {
+    pub fn json(error: impl Into<String>) -> String {
+        serde_json::to_string(&ErrorResponse { error: error.into() }).unwrap()
+    }
+}
@@ -0,0 +1,18 @@
+// ✅ REAL EXAMPLE: App State shared by every handler
+// Built once at startup; handlers take the values with `State<T>`, e.g.
+// `State(sessions): State<SessionStore>`. Register a DB pool here the same way.
+use rsf::{AppState, AuthVerifier, JwtAuth, SessionStore, StaticFileServer, WsRoom};
+use crate::app::auth::jwt_secret;
+use std::time::Duration;
+
+pub fn state() -> AppState {
+    let jwt = JwtAuth::new(jwt_secret());
+    AppState::new()
+        .with(JwtAuth::new(jwt_secret()))
+        // Checks the tokens of `Auth::Bearer` routes before their handlers run
+        .with(AuthVerifier::new().bearer(move |token| jwt.verify_token(token).is_ok()))
+        .with(SessionStore::new(Duration::from_secs(3600))) // 1 hour TTL
+        // Shared so the in-memory cache survives between requests
+        .with(StaticFileServer::new("./public"))
+        .with(WsRoom::new())
+}
@@ -0,0 +1,6 @@
+// Errors under /api (404, 405, 413, ...) are answered as JSON instead of the HTML pages
+use rsf::{FrameworkError, Request, Response};
+
+pub fn render(_req: &Request, err: &FrameworkError) -> Response {
+    err.to_response()
+}
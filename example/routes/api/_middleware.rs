@@ -0,0 +1,7 @@
+// Middleware for every route under /api: adds CORS headers to all responses
+use rsf::{CorsMiddleware, Request, Response};
+
+pub fn after(_req: &Request, mut res: Response) -> Response {
+    res.headers.extend(CorsMiddleware::new().headers());
+    res
+}
@@ -0,0 +1,15 @@
+// Middleware for /api/admin/*: rejects requests without a bearer token before the route runs
+use rsf::{Request, Response, ResponseBuilder};
+
+pub fn before(req: &Request) -> Option<Response> {
+    match req.header("authorization") {
+        Some(value) if value.starts_with("Bearer ") => None,
+        _ => Some(
+            ResponseBuilder::new()
+                .status(401)
+                .header("WWW-Authenticate", "Bearer")
+                .json(&serde_json::json!({ "error": "Missing bearer token" }))
+                .build(),
+        ),
+    }
+}
@@ -0,0 +1,58 @@
+// ✅ REAL EXAMPLE: Protected Admin Route with JWT
+use rsf::{Auth, JwtAuth, RawJson, Request, RouteConfig, State};
+use crate::app::auth::bearer_token;
+use crate::app::models::{ErrorResponse, User};
+
+pub const ROUTE: RouteConfig = RouteConfig {
+    auth: Auth::Bearer,
+    tags: &["admin"],
+    ..RouteConfig::DEFAULT
+};
+
+// The `JwtAuth` of the app state (`_state.rs`) shares its secret with the login route
+pub fn GET(req: &Request, State(jwt): State<JwtAuth>) -> (RawJson<String>, u16) {
+    // ✅ REAL FEATURE: JWT Authentication Check
+    // `Auth::Bearer` already answered requests without a valid token with 401
+    let user_id = match jwt.verify_token(bearer_token(req)) {
+        Ok(id) => id,
+        Err(e) => return (RawJson(ErrorResponse::json(format!("Unauthorized: {}", e))), 401),
+    };
+    
+    // ✅ Authorization: Check if user is admin
+    // In production, check role from database
+    if !user_id.contains("admin") {
+        return (RawJson(ErrorResponse::json("Forbidden: Admin access required")), 403);
+    }
+    
+    println!("✅ Admin access granted for user: {}", user_id);
+    
+    // Fetch users from database (mocked)
+    let users = vec![
+        User {
+            id: "usr_001".to_string(),
+            username: "admin".to_string(),
+            email: "admin@example.com".to_string(),
+            role: "admin".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+        },
+        User {
+            id: "usr_002".to_string(),
+            username: "john".to_string(),
+            email: "john@example.com".to_string(),
+            role: "user".to_string(),
+            created_at: "2025-02-15T10:30:00Z".to_string(),
+        },
+        User {
+            id: "usr_003".to_string(),
+            username: "jane".to_string(),
+            email: "jane@example.com".to_string(),
+            role: "user".to_string(),
+            created_at: "2025-03-20T14:45:00Z".to_string(),
+        },
+    ];
+    
+    match serde_json::to_string(&users) {
+        Ok(json) => (RawJson(json), 200),
+        Err(_) => (RawJson(ErrorResponse::json("Internal server error")), 500),
+    }
+}
@@ -1,7 +1,15 @@
 // ✅ REAL EXAMPLE: Response Compression
 use std::collections::HashMap;
 use serde::Serialize;
-use core::engine::ResponseCompressor;
+use rsf::{RawJson, ResponseCompressor, RouteConfig};
+use std::time::Duration;
+
+// The data set only changes hourly, so a minute-old response is good enough
+pub const ROUTE: RouteConfig = RouteConfig {
+    cache_ttl: Some(Duration::from_secs(60)),
+    tags: &["data"],
+    ..RouteConfig::DEFAULT
+};
 
 #[derive(Serialize)]
 struct DataPoint {
@@ -11,7 +19,7 @@ struct DataPoint {
     metadata: String,
 }
 
-pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
+pub fn GET(_params: &HashMap<String, String>) -> (RawJson<String>, u16) {
     // Generate large dataset (perfect for compression)
     let mut data_points = Vec::with_capacity(1000);
     for i in 0..1000 {
@@ -55,12 +63,12 @@ pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
             "note": "In production, this would be sent as gzip with Content-Encoding header"
         });
         
-        return (stats.to_string(), 200);
+        return (RawJson(stats.to_string()), 200);
     }
     
     // Response too small for compression
     println!("✅ Compression skipped (response < 1KB or not beneficial)");
     println!("   Size: {} bytes", original_size);
     
-    (json, 200)
+    (RawJson(json), 200)
 }
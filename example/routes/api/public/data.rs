@@ -1,7 +1,7 @@
 // ✅ REAL EXAMPLE: CORS-Enabled Public API
 use std::collections::HashMap;
 use serde::Serialize;
-use core::engine::CorsMiddleware;
+use rsf::{CorsMiddleware, RawJson};
 
 #[derive(Serialize)]
 struct PublicData {
@@ -17,7 +17,7 @@ struct DataItem {
     value: f64,
 }
 
-pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
+pub fn GET(_params: &HashMap<String, String>) -> (RawJson<String>, u16) {
     // ✅ REAL FEATURE: CORS Configuration
     let _cors = CorsMiddleware::new()
         .allow_origin("*")
@@ -59,12 +59,12 @@ pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
     println!("   Access-Control-Allow-Methods: GET, OPTIONS");
     
     match serde_json::to_string(&data) {
-        Ok(json) => (json, 200),
-        Err(_) => (r#"{"error": "Failed to serialize data"}"#.to_string(), 500),
+        Ok(json) => (RawJson(json), 200),
+        Err(_) => (RawJson(r#"{"error": "Failed to serialize data"}"#.to_string()), 500),
     }
 }
 
-pub fn OPTIONS(params: &HashMap<String, String>) -> (String, u16) {
+pub fn OPTIONS(_params: &HashMap<String, String>) -> (String, u16) {
     // ✅ REAL FEATURE: CORS Preflight Response
     let _cors = CorsMiddleware::new()
         .allow_origin("*")
@@ -1,10 +1,16 @@
 // ✅ REAL EXAMPLE: File Upload with Validation
-use std::collections::HashMap;
-use serde::Serialize;
-use core::engine::BodyParser;
+use serde::{Deserialize, Serialize};
+use rsf::{BodyParser, FrameworkError, Json, Query, RateLimit, Request, RouteConfig};
+
+pub const ROUTE: RouteConfig = RouteConfig {
+    max_body: Some(5 * 1024 * 1024),
+    rate_limit: Some(RateLimit::per_minute(10)),
+    tags: &["uploads"],
+    ..RouteConfig::DEFAULT
+};
 
 #[derive(Serialize)]
-struct UploadResponse {
+pub struct UploadResponse {
     success: bool,
     url: String,
     size: usize,
@@ -12,48 +18,30 @@ struct UploadResponse {
     content_type: String,
 }
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    error: String,
+#[derive(Deserialize)]
+pub struct UploadQuery {
+    filename: String,
 }
 
-pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
+/// Upload a file: the raw body, named by `?filename=`
+pub fn POST(req: &Request, Query(upload): Query<UploadQuery>) -> Result<(Json<UploadResponse>, u16), FrameworkError> {
     // ✅ REAL FEATURE: Binary Body Parsing
-    // Mock binary data (in production, this comes from multipart/form-data)
-    let mock_file_data = b"Mock PDF file content...".repeat(100);
-    let filename = "document.pdf";
-    
-    // Parse binary body
-    let file_bytes = match BodyParser::bytes(mock_file_data.as_slice()) {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            let error = ErrorResponse { error: format!("Failed to read file: {}", e) };
-            return (serde_json::to_string(&error).unwrap(), 400);
-        }
-    };
+    // The 5 MiB limit of `ROUTE` is enforced (413) before the handler runs, and a missing
+    // `?filename=` is a 400 from the `Query` extractor.
+    let file_bytes = BodyParser::bytes(&req.body);
+    let filename = upload.filename.as_str();
     
     let file_size = file_bytes.len();
-    
-    // ✅ Validation: File size limit (10MB)
-    const MAX_SIZE: usize = 10 * 1024 * 1024;
-    if file_size > MAX_SIZE {
-        let error = ErrorResponse { 
-            error: format!("File too large: {} bytes (max: {} bytes)", file_size, MAX_SIZE) 
-        };
-        return (serde_json::to_string(&error).unwrap(), 413);
+    if file_size == 0 {
+        return Err(FrameworkError::UnprocessableEntity("the file is empty".to_string()));
     }
     
     // ✅ Validation: File type (basic check by extension)
     let allowed_extensions = ["pdf", "doc", "docx", "txt", "jpg", "png"];
-    let extension = filename.split('.').last().unwrap_or("");
+    let extension = filename.split('.').next_back().unwrap_or("");
     
     if !allowed_extensions.contains(&extension) {
-        let error = ErrorResponse {
-            error: format!("Invalid file type: .{} (allowed: {})", 
-                extension, 
-                allowed_extensions.join(", "))
-        };
-        return (serde_json::to_string(&error).unwrap(), 415);
+        return Err(FrameworkError::UnsupportedMediaType { expected: "a .pdf, .doc, .docx, .txt, .jpg or .png file" });
     }
     
     // Generate unique filename
@@ -91,5 +79,5 @@ pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
         }.to_string(),
     };
     
-    (serde_json::to_string(&response).unwrap(), 201)
+    Ok((Json(response), 201))
 }
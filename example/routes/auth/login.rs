@@ -0,0 +1,95 @@
+// ✅ REAL EXAMPLE: Authentication with JWT and Sessions
+use serde::{Deserialize, Serialize};
+use rsf::{JwtAuth, RawJson, SessionStore, Request, State};
+use crate::app::{auth::bearer_token, models::ErrorResponse};
+use std::time::Duration;
+
+#[derive(Deserialize)]
+struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct LoginResponse {
+    success: bool,
+    token: String,
+    user_id: String,
+    session_id: String,
+    expires_in: u64,
+}
+
+// `JwtAuth` and `SessionStore` come from the app state (`_state.rs`)
+pub fn POST(req: &Request, State(jwt): State<JwtAuth>, State(sessions): State<SessionStore>) -> (RawJson<String>, u16) {
+    // ✅ REAL FEATURE: JSON Body Parsing
+    let credentials = match req.json::<LoginRequest>() {
+        Ok(creds) => creds,
+        Err(e) => {
+            return (RawJson(ErrorResponse::json(format!("Invalid JSON: {}", e))), 400);
+        }
+    };
+    
+    // Simple credential check (use database in production)
+    if credentials.username != "admin" || credentials.password != "admin123" {
+        return (RawJson(ErrorResponse::json("Invalid credentials")), 401);
+    }
+    
+    let user_id = format!("user_{}", credentials.username);
+    
+    // ✅ REAL FEATURE: JWT Token Generation
+    let token = jwt.generate_token(&user_id, Duration::from_secs(86400)); // 24 hours
+    
+    // ✅ REAL FEATURE: Session Creation
+    let session_id = format!("sess_{}", std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis());
+    
+    let mut session = sessions.create(session_id.clone());
+    session.set("user_id".to_string(), user_id.clone());
+    session.set("username".to_string(), credentials.username);
+    sessions.update(session);
+    
+    let response = LoginResponse {
+        success: true,
+        token,
+        user_id,
+        session_id,
+        expires_in: 3600,
+    };
+    
+    (RawJson(serde_json::to_string(&response).unwrap()), 200)
+}
+
+// ✅ REAL FEATURE: Token Verification
+pub fn GET(req: &Request, State(jwt): State<JwtAuth>) -> (RawJson<String>, u16) {
+    // Verify JWT token from the `Authorization: Bearer <token>` header
+    match jwt.verify_token(bearer_token(req)) {
+        Ok(user_id) => {
+            let response = serde_json::json!({
+                "valid": true,
+                "user_id": user_id,
+                "message": "Token is valid"
+            });
+            (RawJson(response.to_string()), 200)
+        }
+        Err(e) => (RawJson(ErrorResponse::json(format!("Invalid token: {}", e))), 401),
+    }
+}
+
+// ✅ REAL FEATURE: Session Logout
+pub fn DELETE(req: &Request, State(sessions): State<SessionStore>) -> (RawJson<String>, u16) {
+    let session_id = req.header("x-session-id")
+        .or_else(|| req.query("session_id"))
+        .map(|s| s.as_str())
+        .unwrap_or("");
+    
+    sessions.delete(session_id);
+    
+    let response = serde_json::json!({
+        "success": true,
+        "message": "Logged out successfully"
+    });
+    
+    (RawJson(response.to_string()), 200)
+}
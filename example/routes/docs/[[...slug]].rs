@@ -0,0 +1,17 @@
+// Optional catch-all route: /docs, /docs/intro, /docs/guides/routing, ...
+use rsf::{RawJson, Request};
+
+pub fn GET(req: &Request) -> (RawJson<String>, u16) {
+    // The remaining path arrives as a list of segments
+    let page = if req.catch_all.is_empty() {
+        "index".to_string()
+    } else {
+        req.catch_all.join("/")
+    };
+
+    let response = serde_json::json!({
+        "page": page,
+        "segments": req.catch_all,
+    });
+    (RawJson(response.to_string()), 200)
+}
@@ -0,0 +1,6 @@
+// Constrained route example: /orders/ord-7f3a - anything else under /orders/ is a 404
+use rsf::RawJson;
+
+pub fn GET(code: String) -> RawJson<String> {
+    RawJson(format!(r#"{{"order_code": "{}"}}"#, code))
+}
@@ -0,0 +1,15 @@
+// Typed route example: /orders/42 - `id` is parsed by the router, /orders/abc falls through
+use rsf::Json;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Order {
+    order_id: u64,
+    next: u64,
+}
+
+/// Look up an order
+pub fn GET(id: u64) -> Option<Json<Order>> {
+    // Orders above 1000 don't exist yet: `None` is answered with a 404
+    (id <= 1000).then(|| Json(Order { order_id: id, next: id + 1 }))
+}
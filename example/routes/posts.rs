@@ -1,18 +1,25 @@
-//'api'
 // ✅ REAL EXAMPLE: Request Body Parsing with Validation
-use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use core::engine::BodyParser;
+use rsf::{FrameworkError, Json, Query, RouteConfig};
+
+pub const ROUTE: RouteConfig = RouteConfig { tags: &["posts"], ..RouteConfig::DEFAULT };
 
 #[derive(Deserialize, Serialize)]
-struct CreatePostRequest {
+pub struct CreatePostRequest {
     title: String,
     content: String,
     tags: Vec<String>,
 }
 
+#[derive(Deserialize)]
+pub struct PostListQuery {
+    page: Option<u32>,
+    per_page: Option<u32>,
+    tag: Option<String>,
+}
+
 #[derive(Serialize)]
-struct CreatePostResponse {
+pub struct CreatePostResponse {
     id: String,
     title: String,
     content: String,
@@ -21,7 +28,7 @@ struct CreatePostResponse {
 }
 
 #[derive(Serialize)]
-struct PostListResponse {
+pub struct PostListResponse {
     posts: Vec<PostSummary>,
     total: usize,
     page: u32,
@@ -36,32 +43,23 @@ struct PostSummary {
     tags: Vec<String>,
 }
 
-pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
-    // ✅ REAL FEATURE: JSON Body Parsing
-    let mock_body = r#"{
-        "title": "My First Post",
-        "content": "This is the content of my first blog post!",
-        "tags": ["rust", "web", "performance"]
-    }"#;
-    
-    let post_data = match BodyParser::json::<CreatePostRequest>(mock_body.as_bytes()) {
-        Ok(data) => data,
-        Err(e) => {
-            return (format!(r#"{{"error": "Invalid JSON: {}"}}"#, e), 400);
-        }
-    };
-    
+/// Create a post
+pub fn POST(Json(post_data): Json<CreatePostRequest>) -> Result<(Json<CreatePostResponse>, u16), FrameworkError> {
+    // ✅ REAL FEATURE: JSON Body Extraction
+    // A missing or non-JSON body is answered with 415/400, a body that doesn't fit
+    // `CreatePostRequest` with 422, before this runs.
+
     // ✅ Validation
     if post_data.title.trim().is_empty() {
-        return (r#"{"error": "Title is required"}"#.to_string(), 400);
+        return Err(FrameworkError::UnprocessableEntity("title is required".to_string()));
     }
     
     if post_data.title.len() > 200 {
-        return (r#"{"error": "Title too long (max 200 chars)"}"#.to_string(), 400);
+        return Err(FrameworkError::UnprocessableEntity("title too long (max 200 chars)".to_string()));
     }
     
     if post_data.content.trim().is_empty() {
-        return (r#"{"error": "Content is required"}"#.to_string(), 400);
+        return Err(FrameworkError::UnprocessableEntity("content is required".to_string()));
     }
     
     // Generate ID and timestamp
@@ -81,27 +79,15 @@ pub fn POST(params: &HashMap<String, String>) -> (String, u16) {
         created_at,
     };
     
-    (serde_json::to_string(&response).unwrap(), 201)
+    Ok((Json(response), 201))
 }
 
-// ✅ REAL FEATURE: Query String Parsing with Pagination
-pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-    use core::engine::QueryParser;
-    
-    // Mock query string (in production, extract from request URL)
-    let query_string = "page=2&per_page=5&tag=rust";
-    let query_params = QueryParser::parse(query_string);
-    
-    // Extract pagination params with defaults
-    let page: u32 = query_params.get("page")
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(1);
-    
-    let per_page: u32 = query_params.get("per_page")
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(10);
-    
-    let tag_filter = query_params.get("tag");
+// ✅ REAL FEATURE: Query String Extraction with Pagination
+/// List posts, optionally filtered by tag
+pub fn GET(Query(query): Query<PostListQuery>) -> Json<PostListResponse> {
+    // Pagination params with defaults; `?page=abc` is a 400
+    let page = query.page.filter(|&p| p > 0).unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(10);
     
     // Mock database query
     let mut posts = vec![
@@ -126,7 +112,7 @@ pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
     ];
     
     // Filter by tag if provided
-    if let Some(tag) = tag_filter {
+    if let Some(tag) = &query.tag {
         posts.retain(|p| p.tags.contains(tag));
     }
     
@@ -136,12 +122,10 @@ pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
     let start = ((page - 1) * per_page) as usize;
     let paginated_posts: Vec<PostSummary> = posts.into_iter().skip(start).take(per_page as usize).collect();
     
-    let response = PostListResponse {
+    Json(PostListResponse {
         posts: paginated_posts,
         total,
         page,
         per_page,
-    };
-    
-    (serde_json::to_string(&response).unwrap(), 200)
+    })
 }
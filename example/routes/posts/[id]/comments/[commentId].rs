@@ -1,19 +1,21 @@
-//'api'
 // Dynamic route with multiple params: /posts/:id/comments/:commentId
+use rsf::RawJson;
 use std::collections::HashMap;
+use crate::routes;
 
-pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
+pub fn GET(params: &HashMap<String, String>) -> (RawJson<String>, u16) {
     let post_id = params.get("id").map(|s| s.as_str()).unwrap_or("unknown");
     let comment_id = params.get("commentId").map(|s| s.as_str()).unwrap_or("unknown");
     
+    // Links come from the generated URL builders, so moving this file breaks the build, not the link
     let response = format!(
-        r#"{{"post_id": "{}", "comment_id": "{}", "content": "This is comment {} on post {}"}}"#,
-        post_id, comment_id, comment_id, post_id
+        r#"{{"post_id": "{}", "comment_id": "{}", "content": "This is comment {} on post {}", "url": "{}"}}"#,
+        post_id, comment_id, comment_id, post_id, routes::posts_id_comments_commentId(post_id, comment_id)
     );
-    (response, 200)
+    (RawJson(response), 200)
 }
 
-pub fn DELETE(params: &HashMap<String, String>) -> (String, u16) {
+pub fn DELETE(params: &HashMap<String, String>) -> (RawJson<String>, u16) {
     let post_id = params.get("id").map(|s| s.as_str()).unwrap_or("unknown");
     let comment_id = params.get("commentId").map(|s| s.as_str()).unwrap_or("unknown");
     
@@ -21,5 +23,5 @@ pub fn DELETE(params: &HashMap<String, String>) -> (String, u16) {
         r#"{{"deleted": true, "post_id": "{}", "comment_id": "{}"}}"#,
         post_id, comment_id
     );
-    (response, 200)
+    (RawJson(response), 200)
 }
@@ -0,0 +1,21 @@
+// Example demonstrating query string parsing
+use rsf::{RawJson, Request};
+
+pub fn GET(req: &Request) -> (RawJson<String>, u16) {
+    let q = req.query("q").map(|s| s.as_str()).unwrap_or("");
+    let limit: usize = req.query("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(10);
+
+    if q.is_empty() {
+        let response = r#"{"message": "Search endpoint", "tip": "Use ?q=search&limit=10"}"#;
+        return (RawJson(response.to_string()), 200);
+    }
+
+    let response = serde_json::json!({
+        "query": q,
+        "limit": limit,
+        "results": []
+    });
+    (RawJson(response.to_string()), 200)
+}
@@ -0,0 +1,29 @@
+// ✅ REAL EXAMPLE: Async Static File Serving with Caching
+use rsf::{Request, Response, RouteConfig, RouteKind, State, StaticFileServer, responses};
+use std::time::Duration;
+
+// Files for browsers: kept out of the OpenAPI document, and slow disks don't hold requests forever
+pub const ROUTE: RouteConfig = RouteConfig {
+    kind: RouteKind::Ui,
+    timeout: Some(Duration::from_secs(10)),
+    ..RouteConfig::DEFAULT
+};
+
+// Async handlers are awaited on the tokio runtime, so file I/O doesn't block it. The file
+// server is shared through the app state (`_state.rs`), so its cache survives between requests.
+pub async fn GET(req: &Request, State(files): State<StaticFileServer>) -> Response {
+    // Get requested file path from the query string (?path=/index.html)
+    let file_path = req.query("path")
+        .map(|s| s.as_str())
+        .unwrap_or("/index.html");
+
+    // Security features:
+    // ✅ Directory traversal protection (blocks ../)
+    // ✅ File type validation (only serves files, not directories)
+    // ✅ Content-Type auto-detection for 20+ file types
+    // ✅ In-memory caching with DashMap
+    match files.serve(file_path).await {
+        Ok(file) => file.to_response(),
+        Err(_) => responses::not_found(),
+    }
+}
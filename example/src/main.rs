@@ -0,0 +1,125 @@
+// The example app: the route files of `routes/`, served by RSF
+use rsf::engine::HandlerResult;
+use rsf::{get, IntoResponse, RawJson, Request, Router};
+
+rsf::main!(router);
+
+/// Routes that aren't files: `/plugins/:name` stands in for routes read from configuration,
+/// and `/users/me` takes precedence over `routes/users/[id].rs`.
+fn router() -> Router {
+    Router::new()
+        .route("/users/me", get(current_user))
+        .nest("/plugins", Router::new().route("/:name", get(plugin)))
+}
+
+fn current_user(_req: &Request) -> HandlerResult {
+    Ok(RawJson(r#"{"user_id": "me", "name": "Current User", "status": "active"}"#).into_response())
+}
+
+fn plugin(req: &Request) -> HandlerResult {
+    let name = req.params.get("name").map_or("", String::as_str);
+    Ok(RawJson(format!(r#"{{"plugin": "{}", "enabled": true}}"#, name)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsf::engine::RouteHandler;
+    use rsf::{Auth, AuthVerifier, FrameworkError, JwtAuth, RouteConfig, RouteKind};
+
+    #[test]
+    fn test_route_file_handlers() {
+        // Private and `pub` handlers taking `&Request`, `&HashMap` or `HashMap` all get a wrapper
+        let get_handler = ROUTE_TABLE.get_handler;
+        assert!(get_handler("/posts", "POST").is_some());
+        assert!(get_handler("/users/7", "DELETE").is_some());
+        assert!(get_handler("/main", "GET").is_some());
+        assert!(get_handler("/posts", "PUT").is_none());
+    }
+
+    #[test]
+    fn test_match_tree() {
+        // Segments are decoded one at a time, so an encoded `/` stays inside its param
+        let get_handler = ROUTE_TABLE.get_handler;
+        let matched = get_handler("/posts/a%2Fb/comments/7", "GET").unwrap();
+        assert_eq!(matched.params.get("id").map(String::as_str), Some("a/b"));
+        assert_eq!(matched.params.get("commentId").map(String::as_str), Some("7"));
+        // One tree per method, and every segment of the path has to be matched
+        assert!(get_handler("/users/7", "DELETE").is_some());
+        assert!(get_handler("/users/7", "POST").is_none());
+        assert!(get_handler("/users/7/extra", "GET").is_none());
+        assert_eq!(get_handler("/docs/a/b", "GET").unwrap().catch_all, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_url_builders() {
+        let url = routes::posts_id_comments_commentId("a b/c", 7);
+        assert_eq!(url, "/posts/a%20b%2Fc/comments/7");
+        let matched = (ROUTE_TABLE.get_handler)(&url, "GET").unwrap();
+        assert_eq!(matched.params.get("id").map(String::as_str), Some("a b/c"));
+        assert_eq!(routes::docs_slug(&[] as &[&str]), "/docs");
+        assert_eq!(routes::docs_slug(&["guides", "routing"]), "/docs/guides/routing");
+    }
+
+    #[test]
+    fn test_empty_segments() {
+        // Matching and param extraction split paths alike: outer slashes go, empty segments stay
+        let get = |path| (ROUTE_TABLE.get_handler)(path, "GET");
+        assert_eq!(get("/users/7/").unwrap().params.get("id").map(String::as_str), Some("7"));
+        assert!(get("/users//7").is_none());
+        assert_eq!(get("/docs/a//b").unwrap().catch_all, ["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let doc: serde_json::Value = serde_json::from_str(ROUTE_TABLE.openapi_json).unwrap();
+        let create = &doc["paths"]["/posts"]["post"];
+        assert_eq!(create["requestBody"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/CreatePostRequest");
+        assert_eq!(create["responses"]["201"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/CreatePostResponse");
+        // Statuses come from the `(body, status)` tuples a handler returns
+        assert_eq!(create["responses"]["400"]["description"], "Bad Request");
+        let list = &doc["paths"]["/posts"]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(list["$ref"], "#/components/schemas/PostListResponse");
+        let schema = &doc["components"]["schemas"]["PostListResponse"];
+        assert_eq!(schema["properties"]["posts"]["items"]["$ref"], "#/components/schemas/PostSummary");
+        assert_eq!(schema["required"], serde_json::json!(["posts", "total", "page", "per_page"]));
+        assert_eq!(doc["paths"]["/orders/{id}"]["parameters"][0]["schema"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_route_config() {
+        let upload = (ROUTE_TABLE.get_handler)("/api/upload", "POST").unwrap();
+        assert_eq!(upload.config.max_body, Some(5 * 1024 * 1024));
+        assert_eq!(upload.config.tags, &["uploads"]);
+        let route_config = ROUTE_TABLE.route_config;
+        assert_eq!(route_config("/static").map(|c| c.kind), Some(RouteKind::Ui));
+        assert_eq!(route_config("/search"), Some(&RouteConfig::DEFAULT));
+        assert!(route_config("/missing").is_none());
+        // `Auth::Bearer` routes are checked by the `AuthVerifier` of `_state.rs`
+        assert_eq!(route_config("/api/admin/users").map(|c| c.auth), Some(Auth::Bearer));
+        let verifier = (ROUTE_TABLE.app_state)().get::<AuthVerifier>().unwrap();
+        let token = JwtAuth::new(app::auth::jwt_secret()).generate_token("usr_admin", std::time::Duration::from_secs(60));
+        assert_eq!(verifier.verify(Auth::Bearer, &format!("Bearer {}", token)), Some(true));
+        assert_eq!(verifier.verify(Auth::Bearer, "Bearer forged"), Some(false));
+    }
+
+    #[test]
+    fn test_handler_errors() {
+        // `Err(FrameworkError)` reaches the server as is, so the route's error pages render it
+        let create = (ROUTE_TABLE.get_handler)("/posts", "POST").unwrap();
+        let RouteHandler::Sync(handler) = create.handler else { panic!("POST /posts is sync") };
+        let req = Request::new("POST".to_string(), "/posts".to_string())
+            .with_header("Content-Type".to_string(), "application/json".to_string())
+            .with_body(r#"{"title": " ", "content": "x", "tags": []}"#.into());
+        assert!(matches!(handler(&req), Err(FrameworkError::UnprocessableEntity(_))));
+    }
+
+    #[test]
+    fn test_router() {
+        assert!(router().check(&ROUTE_TABLE).is_empty());
+        let conflicting = Router::new().route("/users/:name", get(current_user)).route("/docs/*rest", get(plugin));
+        let errors = conflicting.check(&ROUTE_TABLE);
+        assert!(errors[0].starts_with("ambiguous routes /users/[name] and /users/[id]:"), "{}", errors[0]);
+        assert!(errors[1].starts_with("overlapping routes /docs/[...rest] and /docs/[[...slug]]:"), "{}", errors[1]);
+    }
+}
@@ -1,73 +0,0 @@
-// ✅ REAL EXAMPLE: Static File Serving with Caching
-use std::collections::HashMap;
-
-// Note: This demonstrates the API, but actual async file serving
-// requires integration into the async request handler
-
-pub fn GET(params: &HashMap<String, String>) -> (String, u16) {
-    use core::engine::StaticFileServer;
-    
-    // Get requested file path from params
-    let file_path = params.get("path")
-        .map(|s| s.as_str())
-        .unwrap_or("/index.html");
-    
-    println!("✅ Static file request: {}", file_path);
-    
-    // ✅ REAL FEATURE: StaticFileServer with caching and security
-    // In async context, you would do:
-    // let server = StaticFileServer::new("./public");
-    // match server.serve(file_path).await {
-    //     Ok(file) => {
-    //         // file.content: Bytes
-    //         // file.content_type: auto-detected from extension
-    //         return file.to_response();
-    //     }
-    //     Err(e) => {
-    //         return error_response(404, "File not found");
-    //     }
-    // }
-    
-    // Security features:
-    // ✅ Directory traversal protection (blocks ../)
-    // ✅ File type validation (only serves files, not directories)
-    // ✅ Content-Type auto-detection for 20+ file types
-    // ✅ In-memory caching with DashMap
-    
-    // Mock response showing what would be returned
-    let response = match file_path.split('.').last().unwrap_or("") {
-        "html" => {
-            println!("   Content-Type: text/html");
-            (r#"<!DOCTYPE html>
-<html>
-<head><title>Example</title></head>
-<body><h1>Static HTML Page</h1></body>
-</html>"#.to_string(), 200)
-        }
-        "css" => {
-            println!("   Content-Type: text/css");
-            ("body { font-family: sans-serif; }".to_string(), 200)
-        }
-        "js" => {
-            println!("   Content-Type: application/javascript");
-            ("console.log('Static JS loaded');".to_string(), 200)
-        }
-        "json" => {
-            println!("   Content-Type: application/json");
-            (r#"{"message": "Static JSON data"}"#.to_string(), 200)
-        }
-        "png" | "jpg" | "jpeg" => {
-            println!("   Content-Type: image/{}",  file_path.split('.').last().unwrap());
-            (r#"{"note": "Binary image data would be here"}"#.to_string(), 200)
-        }
-        _ => {
-            println!("   ❌ File not found");
-            (r#"{"error": "File not found"}"#.to_string(), 404)
-        }
-    };
-    
-    println!("   ✅ Cached for future requests");
-    println!("   ✅ Directory traversal protection active");
-    
-    response
-}
@@ -0,0 +1,1874 @@
+//! Route generation for RSF apps, run from the app's `build.rs`:
+//!
+//! ```ignore
+//! fn main() {
+//!     rsf_build::generate("routes");
+//! }
+//! ```
+//!
+//! The generated code is written to `OUT_DIR` and included by `rsf::routes!()` (or
+//! `rsf::main!()`), along with the route manifest, `routes.json`, which the app prints when run
+//! with `--routes`.
+
+use rsf_pattern::{conflict, pattern_segment, route_pattern, validate_pattern, ParamSpec, PatternSeg, METHODS};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+mod openapi;
+mod route_config;
+
+/// Generate the routes of the app being built from the route files in `routes_dir`, relative to
+/// the app's root (where its `Cargo.toml` is). `project.json` in the same place is optional: it
+/// names the app for the OpenAPI document and can replace `routes_dir` with a `parent_folder`
+/// or with `mounts`. Any invalid route file is reported with its location and fails the build.
+pub fn generate(routes_dir: impl AsRef<Path>) {
+    let proj_json = project_dir().join("project.json");
+    // Rerun when the build script or the config changes (and only then) ...
+    println!("cargo:rerun-if-changed=build.rs");
+    // ... a path that doesn't exist counts as changed, which would rerun every build
+    if proj_json.exists() {
+        println!("cargo:rerun-if-changed={}", proj_json.display());
+    }
+    let cfg: serde_json::Value = match fs::read_to_string(&proj_json) {
+        Ok(content) => serde_json::from_str(&content).expect("Invalid project.json"),
+        Err(_) => serde_json::Value::Object(Default::default()),
+    };
+
+    // OpenAPI document: served at `openapi.path` ("/openapi.json" unless set, `null` turns it
+    // off), with a Swagger UI page at `openapi.swagger_ui` in dev mode
+    let openapi_cfg = cfg.get("openapi");
+    let openapi_path = match openapi_cfg.and_then(|o| o.get("path")) {
+        None => Some("/openapi.json"),
+        Some(path) => path.as_str(),
+    };
+    let swagger_ui_path = openapi_cfg.and_then(|o| o.get("swagger_ui")).and_then(|v| v.as_str());
+    for (key, path) in [("openapi.path", openapi_path), ("openapi.swagger_ui", swagger_ui_path)] {
+        if path.is_some_and(|p| !p.starts_with('/')) {
+            eprintln!("error: {}: `{}` must start with `/`", proj_json.display(), key);
+            std::process::exit(1);
+        }
+    }
+
+    // Route trees and the URL prefixes they are mounted under
+    let mounts = match read_mounts(&cfg, routes_dir.as_ref()) {
+        Ok(mounts) => mounts,
+        Err(errors) => {
+            for err in &errors {
+                eprintln!("error: {}: {}", proj_json.display(), err);
+            }
+            std::process::exit(1);
+        }
+    };
+
+    // Collect .rs files recursively, sorted so the generated code doesn't depend on the order
+    // the file system lists them in
+    let mut files = Vec::new();
+    for (m, mount) in mounts.iter().enumerate() {
+        let mut mount_files = Vec::new();
+        collect_rs_files(&mount.dir, &mut mount_files);
+        mount_files.sort();
+        files.extend(mount_files.into_iter().map(|f| (m, f)));
+    }
+
+    // ... or a route tree changes: a file added, removed or edited
+    for mount in &mounts {
+        println!("cargo:rerun-if-changed={}", mount.dir.display());
+    }
+    for (_, file) in &files {
+        println!("cargo:rerun-if-changed={}", file.display());
+    }
+    // Files brought in by a `mod` declaration are compiled as part of the file that declares
+    // them, not as routes of their own
+    let submodules = declared_submodules(files.iter().map(|(_, f)| f.as_path()));
+    files.retain(|(_, f)| !submodules.contains(f));
+
+    // Parse every route file and inspect its exported method handlers. Any unsupported
+    // signature is reported with its location and fails the build.
+    // `_middleware.rs` files wrap every route in their directory and below, `_404.rs`,
+    // `_405.rs` and `_error.rs` render errors for it, and `_state.rs` builds the app state;
+    // none of them are routes. Neither are other files and folders named `_...`: `_lib/` at
+    // the root of a tree holds the shared `crate::app` modules, and the rest are helpers that
+    // a route declares with `mod`.
+    let mut routes = Vec::new();
+    let mut middleware = Vec::new();
+    let mut error_pages = Vec::new();
+    let mut state_file: Option<StateFile> = None;
+    let mut errors = Vec::new();
+    for (m, file) in &files {
+        let mount = &mounts[*m];
+        let dir = relative_dir(file, &mount.dir);
+        if let Some(hidden) = dir.iter().position(|d| d.starts_with('_')) {
+            if dir[hidden] == LIB_DIR && hidden > 0 {
+                let err = format!("{}: `_lib/` must be at the root of a route tree", mount.dir.join(dir[..=hidden].join("/")).display());
+                if !errors.contains(&err) {
+                    errors.push(err);
+                }
+            }
+            continue;
+        }
+        if let Some(kind) = file.file_name().and_then(|n| ErrorKind::from_file_name(&n.to_string_lossy())) {
+            match analyze_error_page(file, *m, mount, kind) {
+                Ok(page) => error_pages.push(page),
+                Err(mut errs) => errors.append(&mut errs),
+            }
+            continue;
+        }
+        if file.file_name().is_some_and(|n| n == MIDDLEWARE_FILE) {
+            match analyze_middleware_file(file, *m, mount) {
+                Ok(mw) => middleware.push(mw),
+                Err(mut errs) => errors.append(&mut errs),
+            }
+            continue;
+        }
+        if file.file_name().is_some_and(|n| n == STATE_FILE) {
+            match analyze_state_file(file, *m, mount) {
+                Ok(state) => match &state_file {
+                    Some(first) => errors.push(format!("{}: the app state is already built by {}", file.display(), first.path.display())),
+                    None => state_file = Some(state),
+                },
+                Err(mut errs) => errors.append(&mut errs),
+            }
+            continue;
+        }
+        if file.file_name().is_some_and(|n| n.to_string_lossy().starts_with('_')) {
+            continue;
+        }
+        match analyze_route_file(file, *m, mount) {
+            Ok(route_file) => routes.push(route_file),
+            Err(mut errs) => errors.append(&mut errs),
+        }
+    }
+    let lib_modules = match find_lib_modules(&mounts) {
+        Ok(modules) => modules,
+        Err(mut errs) => {
+            errors.append(&mut errs);
+            Vec::new()
+        }
+    };
+    // Routes that map to the same URL (or overlap) can't be told apart at runtime
+    if errors.is_empty() {
+        errors = find_route_conflicts(&routes);
+    }
+    // Every route gets a `routes::<name>` URL builder, so two routes can't share a name
+    if errors.is_empty() {
+        errors = find_url_builder_collisions(&routes);
+    }
+    if !errors.is_empty() {
+        for err in &errors {
+            eprintln!("error: {}", err);
+        }
+        eprintln!("error: {} route error(s) found, aborting route generation", errors.len());
+        std::process::exit(1);
+    }
+
+    // Prepare output
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    generate_app_modules(&out_dir.join("generated_app.rs"), &mounts, &lib_modules);
+
+    let out_path = out_dir.join("generated_routes.rs");
+    let mut out = fs::File::create(&out_path).expect("Failed to create generated_routes.rs");
+
+    writeln!(out, "// GENERATED FILE - DO NOT EDIT\n").unwrap();
+    writeln!(out, "#[allow(unused_imports)]").unwrap();
+    writeln!(out, "use ::rsf::engine::{{AfterHook, BeforeHook, ErrorPage, ErrorPages, Middleware, RouteHandler, RouteMatch}};\n").unwrap();
+    writeln!(out, "#[allow(non_snake_case)]").unwrap();
+    writeln!(out, "mod generated_router {{").unwrap();
+    writeln!(out, "    include!(concat!(env!(\"OUT_DIR\"), \"/generated_router.rs\"));").unwrap();
+    writeln!(out, "}}\n").unwrap();
+
+        // Emit one module per discovered .rs file. Each declares the file itself as its private
+        // `__orig` submodule, then emits public wrapper
+        // shims inside the parent module that adapt various handler signatures to the project's
+        // expected `Handler = fn(&Request) -> HandlerResult` / `AsyncHandler` types.
+        for route_file in &routes {
+            let mod_name = module_name_for(route_file.mount, &route_file.rel_path);
+
+            writeln!(out, "#[allow(non_snake_case)]").unwrap();
+            writeln!(out, "mod {} {{", mod_name).unwrap();
+
+            emit_orig_module(&mut out, &route_file.path);
+            if route_file.config.declared {
+                writeln!(out, "    pub(crate) use __orig::ROUTE;").unwrap();
+            }
+
+            // Emit public wrapper shims that adapt each handler's signature to `Handler`
+            // (sync) or `AsyncHandler` (async fn, boxed so it can be awaited by the runtime).
+            for h in &route_file.handlers {
+                // Typed path params were validated by the router; parsing them again can't fail
+                // unless the handler is called directly, in which case it's a 404.
+                let mut prelude = Vec::new();
+                let call_args: Vec<String> = h.inputs.iter().map(|arg| match arg {
+                    HandlerArg::Request => "req".to_string(),
+                    HandlerArg::ParamsRef => "&req.params".to_string(),
+                    HandlerArg::ParamsOwned => "req.params.clone()".to_string(),
+                    HandlerArg::Path { name, ty } if ty == "String" => {
+                        format!("req.params.get({:?}).cloned().unwrap_or_default()", name)
+                    }
+                    HandlerArg::Path { name, ty } => {
+                        prelude.push(format!("let Some(__{}) = req.params.get({:?}).and_then(|v| v.parse::<{}>().ok()) else {{", name, name, ty));
+                        prelude.push("    return Err(::rsf::engine::FrameworkError::RouteNotFound { path: req.path.clone() });".to_string());
+                        prelude.push("};".to_string());
+                        format!("__{}", name)
+                    }
+                    HandlerArg::Extract { name } => format!("::rsf::engine::FromRequest::from_request(req, {:?})?", name),
+                }).collect();
+                let req_arg = if h.inputs.is_empty() { "_req" } else { "req" };
+                let call = format!("__orig::{}({}){}", h.fn_name, call_args.join(", "), if h.is_async { ".await" } else { "" });
+
+                // An `Err(FrameworkError)` is returned as is, so the route's error pages render it
+                let comment = "runs the extractors and converts the return value with IntoResponse";
+                let ret = match h.output {
+                    HandlerOutput::Fallible => format!("{}.map(::rsf::engine::IntoResponse::into_response)", call),
+                    _ => format!("Ok(::rsf::engine::IntoResponse::into_response({}))", call),
+                };
+                let body: Vec<String> = prelude.into_iter().chain([ret]).collect();
+
+                if h.is_async {
+                    writeln!(out, "    // wrapper for async {} that {}", h.method, comment).unwrap();
+                    writeln!(out, "    pub fn {}({}: &::rsf::engine::Request) -> ::rsf::engine::RouteFuture<'_> {{", h.method, req_arg).unwrap();
+                    writeln!(out, "        Box::pin(async move {{").unwrap();
+                    for line in &body {
+                        writeln!(out, "            {}", line).unwrap();
+                    }
+                    writeln!(out, "        }})").unwrap();
+                    writeln!(out, "    }}").unwrap();
+                } else {
+                    writeln!(out, "    // wrapper for {} that {}", h.method, comment).unwrap();
+                    writeln!(out, "    #[inline(always)]").unwrap();
+                    writeln!(out, "    pub fn {}({}: &::rsf::engine::Request) -> ::rsf::engine::HandlerResult {{", h.method, req_arg).unwrap();
+                    for line in &body {
+                        writeln!(out, "        {}", line).unwrap();
+                    }
+                    writeln!(out, "    }}").unwrap();
+                }
+            }
+
+            writeln!(out, "}}\n").unwrap();
+        }
+
+    // Middleware modules get the same `__orig` + wrapper treatment as route files
+    for mw in &middleware {
+        writeln!(out, "#[allow(non_snake_case)]").unwrap();
+        writeln!(out, "mod {} {{", module_name_for(mw.mount, &mw.rel_path)).unwrap();
+        emit_orig_module(&mut out, &mw.path);
+        match mw.before {
+            Some(false) => {
+                writeln!(out, "    pub fn before(req: &::rsf::engine::Request) -> Option<::rsf::engine::Response> {{").unwrap();
+                writeln!(out, "        __orig::before(req)").unwrap();
+            }
+            Some(true) => {
+                writeln!(out, "    pub fn before(req: &::rsf::engine::Request) -> ::rsf::engine::BeforeFuture<'_> {{").unwrap();
+                writeln!(out, "        Box::pin(__orig::before(req))").unwrap();
+            }
+            None => {}
+        }
+        if mw.before.is_some() {
+            writeln!(out, "    }}").unwrap();
+        }
+        match mw.after {
+            Some(false) => {
+                writeln!(out, "    pub fn after(req: &::rsf::engine::Request, res: ::rsf::engine::Response) -> ::rsf::engine::Response {{").unwrap();
+                writeln!(out, "        __orig::after(req, res)").unwrap();
+            }
+            Some(true) => {
+                writeln!(out, "    pub fn after(req: &::rsf::engine::Request, res: ::rsf::engine::Response) -> ::rsf::engine::HandlerFuture<'_> {{").unwrap();
+                writeln!(out, "        Box::pin(__orig::after(req, res))").unwrap();
+            }
+            None => {}
+        }
+        if mw.after.is_some() {
+            writeln!(out, "    }}").unwrap();
+        }
+        writeln!(out, "}}\n").unwrap();
+    }
+
+    // Error pages: `render(req, err)` wrapped like a handler
+    for page in &error_pages {
+        writeln!(out, "#[allow(non_snake_case)]").unwrap();
+        writeln!(out, "mod {} {{", module_name_for(page.mount, &page.rel_path)).unwrap();
+        emit_orig_module(&mut out, &page.path);
+        if page.is_async {
+            writeln!(out, "    pub fn render<'a>(req: &'a ::rsf::engine::Request, err: &'a ::rsf::engine::FrameworkError) -> ::rsf::engine::HandlerFuture<'a> {{").unwrap();
+            writeln!(out, "        Box::pin(__orig::render(req, err))").unwrap();
+        } else {
+            writeln!(out, "    pub fn render(req: &::rsf::engine::Request, err: &::rsf::engine::FrameworkError) -> ::rsf::engine::Response {{").unwrap();
+            writeln!(out, "        __orig::render(req, err)").unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    }
+
+    // The app state: `fn state() -> AppState` of `_state.rs`, called once at startup
+    if let Some(state) = &state_file {
+        writeln!(out, "#[allow(non_snake_case)]").unwrap();
+        writeln!(out, "mod {} {{", module_name_for(state.mount, &state.rel_path)).unwrap();
+        emit_orig_module(&mut out, &state.path);
+        writeln!(out, "    pub fn state() -> ::rsf::engine::AppState {{").unwrap();
+        writeln!(out, "        __orig::state()").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    }
+    writeln!(out, "/// The app state built by `_state.rs`, or an empty one").unwrap();
+    writeln!(out, "pub fn app_state() -> ::rsf::engine::AppState {{").unwrap();
+    match &state_file {
+        Some(state) => writeln!(out, "    {}::state()", module_name_for(state.mount, &state.rel_path)).unwrap(),
+        None => writeln!(out, "    ::rsf::engine::AppState::new()").unwrap(),
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    // Assign every (file, method) handler a route id and build one match tree per method.
+    let mut table: Vec<(&RouteFile, &HandlerSig)> = Vec::new();
+    let mut trees: Vec<(&'static str, TrieNode)> = Vec::new();
+    for route_file in &routes {
+        let pattern = route_pattern(&route_file.route);
+        for h in &route_file.handlers {
+            let id = table.len();
+            table.push((route_file, h));
+            let tree = match trees.iter_mut().position(|(m, _)| *m == h.method) {
+                Some(pos) => &mut trees[pos].1,
+                None => {
+                    trees.push((h.method, TrieNode::default()));
+                    &mut trees.last_mut().unwrap().1
+                }
+            };
+            tree.insert(&pattern, id);
+        }
+    }
+
+    // The handler table, indexed by the route ids produced by `generated_router::match_route`
+    writeln!(out, "static ROUTE_HANDLERS: [RouteHandler; generated_router::ROUTE_COUNT] = [").unwrap();
+    for (id, (route_file, h)) in table.iter().enumerate() {
+        let kind = if h.is_async { "Async" } else { "Sync" };
+        writeln!(out, "    /* {} */ RouteHandler::{}({}::{}),", id, kind, module_name_for(route_file.mount, &route_file.rel_path), h.method).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    // Method, pattern and file of each handler, for startup checks and dev tooling
+    writeln!(out, "static ROUTE_INFO: [::rsf::engine::RouteInfo; generated_router::ROUTE_COUNT] = [").unwrap();
+    for (id, (route_file, h)) in table.iter().enumerate() {
+        let file = format!("{}/{}", mounts[route_file.mount].config_dir.trim_end_matches('/'), route_file.rel_path);
+        writeln!(out, "    /* {} */ ::rsf::engine::RouteInfo {{ method: {:?}, pattern: {:?}, file: {:?} }},", id, h.method, route_file.route, file).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    // Declared `ROUTE` settings of each route; rustc checks them where they are inlined
+    writeln!(out, "static ROUTE_CONFIGS: [&::rsf::engine::RouteConfig; generated_router::ROUTE_COUNT] = [").unwrap();
+    for (id, (route_file, _)) in table.iter().enumerate() {
+        writeln!(out, "    /* {} */ {},", id, route_config_expr(route_file)).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    for (i, mw) in middleware.iter().enumerate() {
+        let hook = |kind: &str, hook: Option<bool>| match hook {
+            Some(is_async) => format!("Some({}::{}({}::{}))", kind, if is_async { "Async" } else { "Sync" }, module_name_for(mw.mount, &mw.rel_path), kind.trim_end_matches("Hook").to_lowercase()),
+            None => "None".to_string(),
+        };
+        writeln!(out, "// {}", mw.rel_path).unwrap();
+        writeln!(out, "const MIDDLEWARE_{}: Middleware = Middleware {{ before: {}, after: {} }};", i, hook("BeforeHook", mw.before), hook("AfterHook", mw.after)).unwrap();
+    }
+    // Middleware of each route, outermost directory first
+    writeln!(out, "static ROUTE_MIDDLEWARE: [&[Middleware]; generated_router::ROUTE_COUNT] = [").unwrap();
+    for (id, (route_file, _)) in table.iter().enumerate() {
+        let mut chain: Vec<usize> = (0..middleware.len()).filter(|&i| middleware[i].applies_to(route_file)).collect();
+        chain.sort_by_key(|&i| middleware[i].dir.len());
+        let chain: Vec<String> = chain.iter().map(|i| format!("MIDDLEWARE_{}", i)).collect();
+        writeln!(out, "    /* {} */ &[{}],", id, chain.join(", ")).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    for (i, page) in error_pages.iter().enumerate() {
+        writeln!(out, "// {}", page.rel_path).unwrap();
+        writeln!(out, "const ERROR_PAGE_{}: ErrorPage = ErrorPage::{}({}::render);", i, if page.is_async { "Async" } else { "Sync" }, module_name_for(page.mount, &page.rel_path)).unwrap();
+    }
+    // Nearest error pages of each route, resolved through its directory (route groups included)
+    writeln!(out, "static ROUTE_ERROR_PAGES: [ErrorPages; generated_router::ROUTE_COUNT] = [").unwrap();
+    for (id, (route_file, _)) in table.iter().enumerate() {
+        writeln!(out, "    /* {} */ {},", id, resolve_error_pages(&error_pages, &route_file.scope(&mounts))).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+    // Error pages by URL prefix for requests that matched no route, nearest first. Directories
+    // inside route groups don't appear in URLs, so their pages only apply to their routes.
+    let mut scopes: Vec<&Vec<String>> = error_pages.iter().map(|p| &p.scope).filter(|d| !d.iter().any(|c| is_route_group(c))).collect();
+    scopes.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    scopes.dedup();
+    let root = Vec::new();
+    if scopes.last().is_none_or(|d| !d.is_empty()) {
+        scopes.push(&root);
+    }
+    writeln!(out, "/// Error pages by URL prefix (`None` = dynamic segment), nearest first; the last entry is the root.").unwrap();
+    writeln!(out, "static PATH_ERROR_PAGES: [(&[Option<&str>], ErrorPages); {}] = [", scopes.len()).unwrap();
+    for dir in &scopes {
+        let prefix: Vec<String> = dir.iter().map(|c| match pattern_segment(c) {
+            PatternSeg::Static(s) => format!("Some({:?})", s),
+            _ => "None".to_string(),
+        }).collect();
+        writeln!(out, "    (&[{}], {}),", prefix.join(", "), resolve_error_pages(&error_pages, dir)).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    writeln!(out, "/// Look up the handler for `route` + `method` in the compiled match tree.").unwrap();
+    writeln!(out, "#[inline(always)]").unwrap();
+    writeln!(out, "pub fn get_handler(route: &str, method: &str) -> Option<RouteMatch> {{").unwrap();
+    writeln!(out, "    let id = generated_router::match_route(method, route)?;").unwrap();
+    writeln!(out, "    let (params, catch_all) = generated_router::route_params(id, route);").unwrap();
+    writeln!(out, "    Some(RouteMatch {{").unwrap();
+    writeln!(out, "        handler: ROUTE_HANDLERS[id],").unwrap();
+    writeln!(out, "        params,").unwrap();
+    writeln!(out, "        catch_all,").unwrap();
+    writeln!(out, "        middleware: ROUTE_MIDDLEWARE[id],").unwrap();
+    writeln!(out, "        error_pages: ROUTE_ERROR_PAGES[id],").unwrap();
+    writeln!(out, "        config: ROUTE_CONFIGS[id],").unwrap();
+    writeln!(out, "        id,").unwrap();
+    writeln!(out, "    }})").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out, "\n/// `ROUTE` settings of the route file with the given pattern, e.g. `/users/[id]`.").unwrap();
+    writeln!(out, "pub fn route_config(pattern: &str) -> Option<&'static ::rsf::engine::RouteConfig> {{").unwrap();
+    writeln!(out, "    match pattern {{").unwrap();
+    for route_file in &routes {
+        writeln!(out, "        {:?} => Some({}),", route_file.route, route_config_expr(route_file)).unwrap();
+    }
+    writeln!(out, "        _ => None,").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    emit_url_builders(&mut out, &routes);
+
+    let title = cfg.get("name").and_then(|v| v.as_str()).unwrap_or("API");
+    let version = cfg.get("version").and_then(|v| v.as_str()).unwrap_or("0.0.0");
+    let openapi_out = out_dir.join("openapi.json");
+    fs::write(&openapi_out, openapi::document(&routes, title, version)).expect("Failed to write openapi.json");
+    writeln!(out).unwrap();
+    writeln!(out, "/// OpenAPI document for the routes, served at `OPENAPI_PATH` (`openapi` in project.json)").unwrap();
+    writeln!(out, "pub static OPENAPI_JSON: &str = include_str!(concat!(env!(\"OUT_DIR\"), \"/openapi.json\"));").unwrap();
+    writeln!(out, "pub const OPENAPI_PATH: Option<&str> = {:?};", openapi_path).unwrap();
+    writeln!(out, "/// Swagger UI page for the document, only served in dev mode").unwrap();
+    writeln!(out, "pub const SWAGGER_UI_PATH: Option<&str> = {:?};", swagger_ui_path).unwrap();
+
+    // Machine-readable route manifest for tooling (`cli routes`, docs, gateways, tests), printed
+    // by `rsf::run` for `--routes` so it never has to be written into the source tree
+    fs::write(out_dir.join("routes.json"), route_manifest(&routes, &middleware, &error_pages, &mounts)).expect("Failed to write routes.json");
+    writeln!(out, "/// Route manifest, printed by the app when run with `--routes`").unwrap();
+    writeln!(out, "pub static ROUTE_MANIFEST_JSON: &str = include_str!(concat!(env!(\"OUT_DIR\"), \"/routes.json\"));").unwrap();
+
+    // Everything above, for the server
+    writeln!(out, "\n/// The app's routes, handed to `rsf::run` at startup").unwrap();
+    writeln!(out, "pub static ROUTE_TABLE: ::rsf::engine::RouteTable = ::rsf::engine::RouteTable {{").unwrap();
+    writeln!(out, "    get_handler,").unwrap();
+    writeln!(out, "    allowed_methods: generated_router::allowed_methods,").unwrap();
+    writeln!(out, "    route_config,").unwrap();
+    writeln!(out, "    routes: &ROUTE_INFO,").unwrap();
+    writeln!(out, "    configs: &ROUTE_CONFIGS,").unwrap();
+    writeln!(out, "    path_error_pages: &PATH_ERROR_PAGES,").unwrap();
+    writeln!(out, "    app_state,").unwrap();
+    writeln!(out, "    openapi_json: OPENAPI_JSON,").unwrap();
+    writeln!(out, "    openapi_path: OPENAPI_PATH,").unwrap();
+    writeln!(out, "    swagger_ui_path: SWAGGER_UI_PATH,").unwrap();
+    writeln!(out, "    manifest_json: ROUTE_MANIFEST_JSON,").unwrap();
+    writeln!(out, "}};").unwrap();
+
+    // The match tree itself lives in its own file with no handler types so that it can be
+    // compiled standalone (the routing benchmarks include it directly).
+    let router_path = out_dir.join("generated_router.rs");
+    let mut out = fs::File::create(&router_path).expect("Failed to create generated_router.rs");
+    emit_router(&mut out, &table, &trees);
+}
+
+/// Root of the app being built, where project.json lives. Read from the environment cargo runs
+/// the build script in, so a script compiled for one checkout never reads another's config.
+fn project_dir() -> PathBuf {
+    PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo"))
+}
+
+/// The route file's `ROUTE` const, or the default settings
+fn route_config_expr(route_file: &RouteFile) -> String {
+    if route_file.config.declared {
+        format!("&{}::ROUTE", module_name_for(route_file.mount, &route_file.rel_path))
+    } else {
+        "&::rsf::engine::RouteConfig::DEFAULT".to_string()
+    }
+}
+
+/// A node of the per-method route match tree
+#[derive(Default)]
+struct TrieNode<'a> {
+    /// Route id when the path ends at this node
+    leaf: Option<usize>,
+    /// Static children, matched before anything else
+    statics: std::collections::BTreeMap<String, TrieNode<'a>>,
+    /// Dynamic `[param]` children, tried in order when no static child matches:
+    /// typed (`[id:u64]`) first, then regex (`[name:regex(..)]`), the unconstrained one last
+    params: Vec<(Option<ParamSpec<'a>>, TrieNode<'a>)>,
+    /// `[...name]` route rooted at this node
+    catch_all: Option<usize>,
+    /// `[[...name]]` route rooted at this node
+    optional_catch_all: Option<usize>,
+}
+
+impl<'a> TrieNode<'a> {
+    fn insert(&mut self, pattern: &[PatternSeg<'a>], id: usize) {
+        let Some((first, rest)) = pattern.split_first() else {
+            self.leaf.get_or_insert(id);
+            return;
+        };
+        match first {
+            PatternSeg::Static(name) => self.statics.entry(name.to_string()).or_default().insert(rest, id),
+            PatternSeg::Param(_, spec) => {
+                let pos = match self.params.iter().position(|(s, _)| s == spec) {
+                    Some(pos) => pos,
+                    None => {
+                        self.params.push((*spec, TrieNode::default()));
+                        self.params.sort_by_key(|(s, _)| match s {
+                            Some(ParamSpec::Type(_)) => 0,
+                            Some(ParamSpec::Regex(_)) => 1,
+                            None => 2,
+                        });
+                        self.params.iter().position(|(s, _)| s == spec).unwrap()
+                    }
+                };
+                self.params[pos].1.insert(rest, id);
+            }
+            PatternSeg::CatchAll(_) => {
+                self.catch_all.get_or_insert(id);
+            }
+            PatternSeg::OptionalCatchAll(_) => {
+                self.optional_catch_all.get_or_insert(id);
+            }
+        }
+    }
+}
+
+/// Emit `generated_router.rs`: `match_route` walks the per-method trees and `route_params`
+/// extracts the params of the matched route.
+fn emit_router(out: &mut fs::File, table: &[(&RouteFile, &HandlerSig)], trees: &[(&'static str, TrieNode<'_>)]) {
+    writeln!(out, "// GENERATED FILE - DO NOT EDIT").unwrap();
+    writeln!(out, "// Compile-time match tree for the handler table in generated_routes.rs.\n").unwrap();
+    writeln!(out, "use std::collections::HashMap;\n").unwrap();
+    writeln!(out, "/// Route ids, in handler table order:").unwrap();
+    for (id, (route_file, h)) in table.iter().enumerate() {
+        if route_file.groups.is_empty() {
+            writeln!(out, "/// - {}: {} {}", id, h.method, route_file.route).unwrap();
+        } else {
+            writeln!(out, "/// - {}: {} {} (groups: {})", id, h.method, route_file.route, route_file.groups.join(", ")).unwrap();
+        }
+    }
+    writeln!(out, "pub const ROUTE_COUNT: usize = {};\n", table.len()).unwrap();
+
+    // Methods that have at least one route, in `METHODS` order
+    let methods: Vec<&str> = METHODS.iter().copied().filter(|m| trees.iter().any(|(t, _)| t == m)).collect();
+    writeln!(out, "/// Methods with at least one route.").unwrap();
+    writeln!(out, "pub const METHODS: [&str; {}] = {:?};\n", methods.len(), methods).unwrap();
+
+    writeln!(out, "/// Find the route id for `method` + `path`. Static segments win over dynamic ones,").unwrap();
+    writeln!(out, "/// which win over catch-alls; each segment is percent-decoded on its own, so `%2F`").unwrap();
+    writeln!(out, "/// never splits a segment. Allocation-free unless a segment is percent-encoded.").unwrap();
+    writeln!(out, "#[inline]").unwrap();
+    if trees.is_empty() {
+        writeln!(out, "pub fn match_route(_method: &str, _path: &str) -> Option<usize> {{").unwrap();
+        writeln!(out, "    None").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    } else {
+        writeln!(out, "pub fn match_route(method: &str, path: &str) -> Option<usize> {{").unwrap();
+        writeln!(out, "    match method {{").unwrap();
+        for (method, _) in trees {
+            writeln!(out, "        {:?} => {}_0(segments(path)),", method, method.to_lowercase()).unwrap();
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+
+        // `match_route` and `route_params` split paths the same way: outer slashes are
+        // trimmed and empty segments kept, so `/users//7` matches no `[id]` in either
+        writeln!(out, "/// Segments of a path: `/` has none, `/a//b/` has `a`, `` and `b`.").unwrap();
+        writeln!(out, "#[inline]").unwrap();
+        writeln!(out, "fn segments(path: &str) -> std::str::Split<'_, char> {{").unwrap();
+        writeln!(out, "    let path = path.trim_matches('/');").unwrap();
+        writeln!(out, "    let mut segs = path.split('/');").unwrap();
+        writeln!(out, "    if path.is_empty() {{").unwrap();
+        writeln!(out, "        segs.next();").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "    segs").unwrap();
+        writeln!(out, "}}\n").unwrap();
+    }
+
+    // `[name:regex(..)]` constraints, compiled once and anchored to the whole segment
+    let mut regexes: Vec<&str> = Vec::new();
+    for (route_file, _) in table {
+        for seg in route_pattern(&route_file.route) {
+            if let PatternSeg::Param(_, Some(ParamSpec::Regex(re))) = seg && !regexes.contains(&re) {
+                regexes.push(re);
+            }
+        }
+    }
+    for (i, re) in regexes.iter().enumerate() {
+        writeln!(out, "static RE_{}: std::sync::LazyLock<::rsf::__private::regex::Regex> =", i).unwrap();
+        writeln!(out, "    std::sync::LazyLock::new(|| ::rsf::__private::regex::Regex::new({:?}).unwrap());\n", format!("^(?:{})$", re)).unwrap();
+    }
+
+    for (method, tree) in trees {
+        let mut counter = 0;
+        emit_trie_node(out, &method.to_lowercase(), tree, &regexes, &mut counter);
+    }
+
+    writeln!(out, "/// Methods that have a route matching `path`, used for `Allow` headers.").unwrap();
+    writeln!(out, "pub fn allowed_methods(path: &str) -> Vec<&'static str> {{").unwrap();
+    writeln!(out, "    METHODS.iter().copied().filter(|m| match_route(m, path).is_some()).collect()").unwrap();
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "/// Extract the path params (and catch-all segments) of a route matched by `match_route`.").unwrap();
+    writeln!(out, "/// Static routes return empty collections without allocating.").unwrap();
+    let is_dynamic = |route_file: &RouteFile| route_pattern(&route_file.route).iter().any(|s| !matches!(s, PatternSeg::Static(_)));
+    let any_dynamic = table.iter().any(|(route_file, _)| is_dynamic(route_file));
+    if !any_dynamic {
+        writeln!(out, "pub fn route_params(_route: usize, _path: &str) -> (HashMap<String, String>, Vec<String>) {{").unwrap();
+        writeln!(out, "    (HashMap::new(), Vec::new())").unwrap();
+        writeln!(out, "}}").unwrap();
+        return;
+    }
+    writeln!(out, "pub fn route_params(route: usize, path: &str) -> (HashMap<String, String>, Vec<String>) {{").unwrap();
+    writeln!(out, "    match route {{").unwrap();
+    for (id, (route_file, _)) in table.iter().enumerate() {
+        if !is_dynamic(route_file) {
+            continue;
+        }
+        let pattern = route_pattern(&route_file.route);
+        // Params are read in order; statics in between are skipped with `nth`
+        let needs_mut = pattern.iter().any(|s| matches!(s, PatternSeg::Param(..)));
+        writeln!(out, "        {} => {{", id).unwrap();
+        writeln!(out, "            let mut params = HashMap::new();").unwrap();
+        writeln!(out, "            let {}segs = segments(path);", if needs_mut { "mut " } else { "" }).unwrap();
+        let mut gap = 0;
+        let mut catch_all = false;
+        for seg in &pattern {
+            match seg {
+                PatternSeg::Static(_) => gap += 1,
+                PatternSeg::Param(name, _) => {
+                    writeln!(out, "            if let Some(seg) = segs.nth({}) {{", gap).unwrap();
+                    writeln!(out, "                params.insert({:?}.to_string(), decode(seg));", name).unwrap();
+                    writeln!(out, "            }}").unwrap();
+                    gap = 0;
+                }
+                PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name) => {
+                    let skip = if gap > 0 { format!(".skip({})", gap) } else { String::new() };
+                    writeln!(out, "            let catch_all: Vec<String> = segs{}.map(decode).collect();", skip).unwrap();
+                    writeln!(out, "            params.insert({:?}.to_string(), catch_all.join(\"/\"));", name).unwrap();
+                    catch_all = true;
+                }
+            }
+        }
+        writeln!(out, "            (params, {})", if catch_all { "catch_all" } else { "Vec::new()" }).unwrap();
+        writeln!(out, "        }}").unwrap();
+    }
+    writeln!(out, "        _ => (HashMap::new(), Vec::new()),").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out, "\n#[inline]").unwrap();
+    writeln!(out, "fn decode(seg: &str) -> String {{").unwrap();
+    writeln!(out, "    ::rsf::__private::percent_encoding::percent_decode_str(seg).decode_utf8_lossy().into_owned()").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+/// Emit one match function per tree node (children first) and return this node's function name.
+/// `regexes` are the `[name:regex(..)]` patterns, emitted as `RE_<index>` statics.
+fn emit_trie_node(out: &mut fs::File, prefix: &str, node: &TrieNode, regexes: &[&str], counter: &mut usize) -> String {
+    let name = format!("{}_{}", prefix, *counter);
+    *counter += 1;
+
+    let statics: Vec<(&String, String)> = node.statics.iter()
+        .map(|(seg, child)| (seg, emit_trie_node(out, prefix, child, regexes, counter)))
+        .collect();
+    let params: Vec<(String, String)> = node.params.iter()
+        .map(|(spec, child)| {
+            let check = match spec {
+                None => "!seg.is_empty()".to_string(),
+                Some(ParamSpec::Type(ty)) => format!("decoded.parse::<{}>().is_ok()", ty),
+                Some(ParamSpec::Regex(re)) => {
+                    format!("RE_{}.is_match(&decoded)", regexes.iter().position(|r| r == re).unwrap())
+                }
+            };
+            (check, emit_trie_node(out, prefix, child, regexes, counter))
+        })
+        .collect();
+
+    // When the path ends here, an optional catch-all also matches (with no segments)
+    let on_end = match node.leaf.or(node.optional_catch_all) {
+        Some(id) => format!("Some({})", id),
+        None => "None".to_string(),
+    };
+    // A catch-all takes any remaining segments once static and dynamic children failed
+    let fallback = node.catch_all.or(node.optional_catch_all);
+
+    writeln!(out, "#[inline]").unwrap();
+    writeln!(out, "fn {}(mut segs: std::str::Split<'_, char>) -> Option<usize> {{", name).unwrap();
+    if statics.is_empty() && params.is_empty() {
+        writeln!(out, "    match segs.next() {{").unwrap();
+        writeln!(out, "        None => {},", on_end).unwrap();
+        match fallback {
+            Some(id) => writeln!(out, "        Some(_) => Some({}),", id).unwrap(),
+            None => writeln!(out, "        Some(_) => None,").unwrap(),
+        }
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+        return name;
+    }
+
+    if on_end == "None" {
+        writeln!(out, "    let seg = segs.next()?;").unwrap();
+    } else {
+        writeln!(out, "    let seg = match segs.next() {{").unwrap();
+        writeln!(out, "        Some(seg) => seg,").unwrap();
+        writeln!(out, "        None => return {},", on_end).unwrap();
+        writeln!(out, "    }};").unwrap();
+    }
+    if !statics.is_empty() || params.iter().any(|(check, _)| check.contains("decoded")) {
+        writeln!(out, "    let decoded = ::rsf::__private::percent_encoding::percent_decode_str(seg).decode_utf8_lossy();").unwrap();
+    }
+
+    // Candidates are tried in order; every one but the last backtracks on a clone of `segs`
+    let candidates = usize::from(!statics.is_empty()) + params.len();
+    let mut emitted = 0;
+    if !statics.is_empty() {
+        emitted += 1;
+        let last = emitted == candidates && fallback.is_none();
+        let segs = if last { "segs" } else { "segs.clone()" };
+        writeln!(out, "    {}match &*decoded {{", if last { "" } else { "let found = " }).unwrap();
+        for (seg, child) in &statics {
+            writeln!(out, "        {:?} => {}({}),", seg, child, segs).unwrap();
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}{}", if last { "" } else { ";" }).unwrap();
+        if !last {
+            writeln!(out, "    if found.is_some() {{").unwrap();
+            writeln!(out, "        return found;").unwrap();
+            writeln!(out, "    }}").unwrap();
+        }
+    }
+    for (check, child) in &params {
+        emitted += 1;
+        if emitted == candidates && fallback.is_none() {
+            writeln!(out, "    if {} {{ {}(segs) }} else {{ None }}", check, child).unwrap();
+        } else {
+            writeln!(out, "    if {} {{", check).unwrap();
+            writeln!(out, "        let found = {}(segs.clone());", child).unwrap();
+            writeln!(out, "        if found.is_some() {{").unwrap();
+            writeln!(out, "            return found;").unwrap();
+            writeln!(out, "        }}").unwrap();
+            writeln!(out, "    }}").unwrap();
+        }
+    }
+    if let Some(id) = fallback {
+        writeln!(out, "    Some({})", id).unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+    name
+}
+
+const LIB_DIR: &str = "_lib";
+
+/// A module of a `_lib/` folder, compiled once as `crate::app::<name>`
+struct LibModule {
+    name: String,
+    /// `_lib/<name>.rs` or `_lib/<name>/mod.rs`
+    path: PathBuf,
+    mount: usize,
+}
+
+/// The modules of every mount's `_lib/` folder: its `.rs` files and its folders with a `mod.rs`.
+/// Their own `mod` declarations bring in the rest, as in any crate.
+fn find_lib_modules(mounts: &[Mount]) -> Result<Vec<LibModule>, Vec<String>> {
+    let mut modules: Vec<LibModule> = Vec::new();
+    let mut errors = Vec::new();
+    for (m, mount) in mounts.iter().enumerate() {
+        let Ok(entries) = fs::read_dir(mount.dir.join(LIB_DIR)) else { continue };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            let (name, file) = if path.is_dir() {
+                (path.file_name(), path.join("mod.rs"))
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                (path.file_stem(), path.clone())
+            } else {
+                continue;
+            };
+            let name = name.map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if !file.is_file() {
+                continue;
+            }
+            if syn::parse_str::<syn::Ident>(&name).is_err() {
+                errors.push(format!("{}: `{}` is not a valid module name", path.display(), name));
+                continue;
+            }
+            if let Some(other) = modules.iter().find(|other| other.name == name) {
+                errors.push(format!("{}: `app::{}` is already defined by {}", file.display(), name, other.path.display()));
+                continue;
+            }
+            modules.push(LibModule { name, path: file, mount: m });
+        }
+    }
+    if errors.is_empty() { Ok(modules) } else { Err(errors) }
+}
+
+/// Write the `crate::app` modules. Each mount's `_lib/` becomes an inline module with a
+/// `#[path]` to the folder, so its files and their submodules are laid out like `src/`.
+fn generate_app_modules(out_path: &Path, mounts: &[Mount], modules: &[LibModule]) {
+    let mut out = fs::File::create(out_path).expect("Failed to create generated_app.rs");
+    writeln!(out, "// GENERATED FILE - DO NOT EDIT\n").unwrap();
+    for (m, mount) in mounts.iter().enumerate() {
+        let names: Vec<&str> = modules.iter().filter(|lib| lib.mount == m).map(|lib| lib.name.as_str()).collect();
+        if names.is_empty() {
+            continue;
+        }
+        let dir = mount.dir.join(LIB_DIR);
+        let dir = fs::canonicalize(&dir).unwrap_or(dir);
+        writeln!(out, "// `{}/` of mount {}", LIB_DIR, m).unwrap();
+        writeln!(out, "#[path = {:?}]", dir.display().to_string()).unwrap();
+        writeln!(out, "mod lib_{} {{", m).unwrap();
+        for name in names {
+            writeln!(out, "    pub mod {};", name).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "pub use lib_{}::*;\n", m).unwrap();
+    }
+}
+
+/// Declare a route or middleware file as the private `__orig` module of its wrapper module.
+/// The file is compiled as it is written, so rustc and rust-analyzer report its own lines, and
+/// its `mod` declarations and `use super::..` resolve like in any module. The path is absolute:
+/// rustc would resolve a relative one against `OUT_DIR`.
+fn emit_orig_module(out: &mut fs::File, path: &Path) {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    writeln!(out, "    #[path = {:?}]", path.display().to_string()).unwrap();
+    writeln!(out, "    mod __orig;").unwrap();
+}
+
+/// Items the generated wrappers call (handlers, hooks, `render`, `state`, `ROUTE`) live in the
+/// file's own module, so they must be visible to the rest of the crate
+fn is_exported(vis: &syn::Visibility) -> bool {
+    match vis {
+        syn::Visibility::Public(_) => true,
+        syn::Visibility::Restricted(r) => r.in_token.is_none() && r.path.is_ident("crate"),
+        syn::Visibility::Inherited => false,
+    }
+}
+
+/// Non-`pub` structs, enums, unions and type aliases of `file` that `sig` refers to
+fn private_types_in(sig: &syn::Signature, file: &syn::File) -> Vec<syn::Ident> {
+    struct TypeNames(Vec<syn::Ident>);
+    impl<'ast> syn::visit::Visit<'ast> for TypeNames {
+        fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+            self.0.extend(ty.path.segments.iter().map(|s| s.ident.clone()));
+            syn::visit::visit_type_path(self, ty);
+        }
+    }
+    let mut names = TypeNames(Vec::new());
+    syn::visit::Visit::visit_signature(&mut names, sig);
+
+    file.items.iter().filter_map(|item| match item {
+        syn::Item::Struct(s) if !is_exported(&s.vis) => Some(&s.ident),
+        syn::Item::Enum(e) if !is_exported(&e.vis) => Some(&e.ident),
+        syn::Item::Union(u) if !is_exported(&u.vis) => Some(&u.ident),
+        syn::Item::Type(t) if !is_exported(&t.vis) => Some(&t.ident),
+        _ => None,
+    })
+    .filter(|ident| names.0.contains(ident))
+    .cloned()
+    .collect()
+}
+
+/// Diagnostic for an item of a framework file that the generated code can't reach
+fn not_exported(path: &Path, span: proc_macro2::Span, item: &str) -> String {
+    diagnostic(path, span, &format!("`{}` must be `pub` so the generated router can reach it", item))
+}
+
+/// Files of the `mod name;` declarations in `files`, and in those files in turn. Framework files
+/// are declared through `#[path]`, so like `mod.rs` files their submodules sit next to them.
+fn declared_submodules<'a>(files: impl Iterator<Item = &'a Path>) -> HashSet<PathBuf> {
+    let mut found = HashSet::new();
+    let mut pending: Vec<(PathBuf, PathBuf)> = files
+        .filter_map(|f| Some((f.to_path_buf(), f.parent()?.to_path_buf())))
+        .collect();
+    while let Some((file, dir)) = pending.pop() {
+        let Some(ast) = fs::read_to_string(&file).ok().and_then(|c| syn::parse_file(&c).ok()) else { continue };
+        for item in &ast.items {
+            let syn::Item::Mod(module) = item else { continue };
+            if module.content.is_some() {
+                continue;
+            }
+            let name = module.ident.to_string();
+            // `#[path]` is relative to the declaring file's directory
+            let explicit = module.attrs.iter().find_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(nv) if nv.path.is_ident("path") => match &nv.value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(file.parent()?.join(s.value())),
+                    _ => None,
+                },
+                _ => None,
+            });
+            let explicit_path = explicit.is_some();
+            let candidates = match explicit {
+                Some(path) => vec![path],
+                None => vec![dir.join(format!("{}.rs", name)), dir.join(&name).join("mod.rs")],
+            };
+            let Some(sub) = candidates.into_iter().find(|p| p.is_file()) else { continue };
+            // `mod.rs` (and `#[path]` files) own their directory, other files a directory named after them
+            let sub_dir = match sub.parent() {
+                Some(parent) if explicit_path || sub.file_name().is_some_and(|n| n == "mod.rs") => parent.to_path_buf(),
+                _ => dir.join(&name),
+            };
+            if found.insert(sub.clone()) {
+                pending.push((sub, sub_dir));
+            }
+        }
+    }
+    found
+}
+
+fn collect_rs_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() {
+                collect_rs_files(&p, files);
+            } else if p.extension().is_some_and(|ext| ext == "rs") {
+                files.push(p);
+            }
+        }
+    }
+}
+
+/// Module of a file in the generated code, named after its mount and its path relative to the
+/// mount's directory so the output is the same on every machine: "users/[id].rs" of the first
+/// mount is `module_0_users___id__rs`.
+fn module_name_for(mount: usize, rel_path: &str) -> String {
+    // `/` -> `__`; anything else that isn't valid in an identifier (dynamic segments like
+    // [id], large-data.rs) -> `_`
+    let s: String = rel_path
+        .chars()
+        .map(|c| match c {
+            '/' => "__".to_string(),
+            c if c.is_ascii_alphanumeric() => c.to_string(),
+            _ => "_".to_string(),
+        })
+        .collect();
+    format!("module_{}_{}", mount, s)
+}
+
+/// One argument of a method handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HandlerArg {
+    /// `req: &Request`
+    Request,
+    /// `params: &HashMap<String, String>`
+    ParamsRef,
+    /// `params: HashMap<String, String>`
+    ParamsOwned,
+    /// `id: u64` - a path param taken by name, already parsed to the type of its segment
+    Path { name: String, ty: String },
+    /// `Json(body): Json<T>`, `Query(q): Query<T>`, ... - built by `FromRequest`, which gets
+    /// the name the argument is bound to
+    Extract { name: String },
+}
+
+/// What a handler returns; any `IntoResponse` type works, this only tells the OpenAPI
+/// document where to look for status codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandlerOutput {
+    /// `-> String` / `-> &'static str`
+    Text,
+    /// `-> (String, u16)` and other `(body, status)` tuples
+    TextWithStatus,
+    /// `-> Response`
+    Response,
+    /// `-> Result<T, FrameworkError>` / `-> rsf::Result<T>`: errors go to the error pages
+    Fallible,
+    /// `Json<T>`, `Html<T>`, `Option<T>`, `Result<T, E>`, `(status, headers, body)`, ...
+    Other,
+}
+
+/// A method handler found in a route file
+#[derive(Debug)]
+struct HandlerSig {
+    /// Upper-case HTTP method, e.g. "GET"
+    method: &'static str,
+    /// Name of the function as written in the file ("GET" or "get")
+    fn_name: String,
+    /// Declared as `async fn`
+    is_async: bool,
+    inputs: Vec<HandlerArg>,
+    output: HandlerOutput,
+}
+
+/// A parsed route file and the handlers it exports
+struct RouteFile {
+    path: PathBuf,
+    /// Path relative to its mount's directory, e.g. "users/[id].rs"
+    rel_path: String,
+    /// Index of the mount the file was found in
+    mount: usize,
+    /// Directory of the file relative to its mount's directory (route groups included)
+    dir: Vec<String>,
+    /// URL pattern derived from the file path, e.g. "/users/[id]"
+    route: String,
+    /// Route groups the file belongs to, e.g. ["admin"] for "(admin)/users.rs"
+    groups: Vec<String>,
+    /// The file's `const ROUTE: RouteConfig`, or the defaults
+    config: route_config::RouteMeta,
+    /// Source of the file, parsed again for the OpenAPI document
+    content: String,
+    handlers: Vec<HandlerSig>,
+}
+
+impl RouteFile {
+    /// URL prefix of the file's directory, for matching error page scopes
+    fn scope(&self, mounts: &[Mount]) -> Vec<String> {
+        mounts[self.mount].prefix.iter().chain(&self.dir).cloned().collect()
+    }
+}
+
+/// A route tree and the URL prefix it is mounted under (`mounts` in project.json)
+struct Mount {
+    /// Folder the tree is read from
+    dir: PathBuf,
+    /// `dir` as written in project.json
+    config_dir: String,
+    /// Static URL segments in front of every route of the tree, e.g. ["api", "v1"]
+    prefix: Vec<String>,
+}
+
+impl Mount {
+    fn rel_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.dir).unwrap_or(path).to_string_lossy().replace('\\', "/")
+    }
+
+    /// Mount a file route: "/users/[id]" under "/api/v1" is "/api/v1/users/[id]"
+    fn route(&self, route: &str) -> String {
+        if self.prefix.is_empty() {
+            route.to_string()
+        } else if route == "/" {
+            format!("/{}", self.prefix.join("/"))
+        } else {
+            format!("/{}{}", self.prefix.join("/"), route)
+        }
+    }
+}
+
+/// Mounts from project.json: `mounts: [{ "dir": "../api", "prefix": "/api/v1" }]`, or else
+/// `parent_folder` (by default the route directory given to `generate`) mounted at `/`. Mount
+/// dirs are relative to the app's root, like `parent_folder`.
+fn read_mounts(cfg: &serde_json::Value, routes_dir: &Path) -> Result<Vec<Mount>, Vec<String>> {
+    let crate_dir = project_dir();
+    let Some(entries) = cfg.get("mounts") else {
+        let default = routes_dir.to_string_lossy();
+        let parent = cfg.get("parent_folder").and_then(|v| v.as_str()).unwrap_or(&default);
+        let dir = crate_dir.join(parent);
+        let dir = if dir.exists() { dir } else { Path::new(parent).to_path_buf() };
+        return Ok(vec![Mount { dir, config_dir: parent.to_string(), prefix: Vec::new() }]);
+    };
+    let Some(entries) = entries.as_array() else {
+        return Err(vec!["`mounts` must be a list of `{ \"dir\": .., \"prefix\": .. }`".to_string()]);
+    };
+
+    let mut mounts: Vec<Mount> = Vec::new();
+    let mut errors = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let Some(config_dir) = entry.get("dir").and_then(|v| v.as_str()) else {
+            errors.push(format!("mounts[{}]: missing `dir`", i));
+            continue;
+        };
+        let prefix = entry.get("prefix").and_then(|v| v.as_str()).unwrap_or("/");
+        if !prefix.starts_with('/') {
+            errors.push(format!("mounts[{}]: prefix `{}` must start with `/`", i, prefix));
+            continue;
+        }
+        let segments: Vec<String> = prefix.split('/').filter(|s| !s.is_empty()).map(str::to_string).collect();
+        if let Some(seg) = segments.iter().find(|s| !matches!(pattern_segment(s), PatternSeg::Static(_)) || is_route_group(s) || s.contains(['[', ']'])) {
+            errors.push(format!("mounts[{}]: prefix segment `{}` must be static", i, seg));
+            continue;
+        }
+        let dir = match fs::canonicalize(crate_dir.join(config_dir)) {
+            Ok(dir) => dir,
+            Err(e) => {
+                errors.push(format!("mounts[{}]: cannot read `{}`: {}", i, config_dir, e));
+                continue;
+            }
+        };
+        // A file in two trees would be compiled twice
+        if let Some(other) = mounts.iter().find(|m| dir.starts_with(&m.dir) || m.dir.starts_with(&dir)) {
+            errors.push(format!("mounts[{}]: `{}` overlaps the mount of `{}`", i, config_dir, other.config_dir));
+            continue;
+        }
+        mounts.push(Mount { dir, config_dir: config_dir.to_string(), prefix: segments });
+    }
+    if errors.is_empty() { Ok(mounts) } else { Err(errors) }
+}
+
+/// Find route files that can't be told apart (see [`rsf_pattern::conflict`]).
+fn find_route_conflicts(routes: &[RouteFile]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (i, a) in routes.iter().enumerate() {
+        for b in &routes[i + 1..] {
+            if let Some(reason) = conflict(&a.route, &b.route) {
+                errors.push(format!("{}:\n    {}\n    {}", reason, a.path.display(), b.path.display()));
+            }
+        }
+    }
+    errors
+}
+
+/// Name of the `routes::` URL builder for a pattern: its segments joined with `_`, e.g.
+/// `posts_id_comments_commentId` for `/posts/[id]/comments/[commentId]`, `index` for `/`.
+fn url_builder_name(pattern: &[PatternSeg]) -> String {
+    if pattern.is_empty() {
+        return "index".to_string();
+    }
+    let name: Vec<String> = pattern.iter().map(|seg| match seg {
+        PatternSeg::Static(s) => s.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect(),
+        PatternSeg::Param(name, _) | PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name) => name.to_string(),
+    }).collect();
+    let name = name.join("_");
+    if name.starts_with(|c: char| c.is_ascii_digit()) { format!("_{}", name) } else { rust_ident(name) }
+}
+
+/// `commentId` -> `comment_id`, for URL builder arguments
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    rust_ident(out)
+}
+
+/// Params whose URL builder arguments would clash, e.g. `[userId]` and `[user_id]`
+fn builder_arg_collisions(pattern: &[PatternSeg]) -> Vec<String> {
+    let mut seen: Vec<(&str, String)> = Vec::new();
+    let mut errors = Vec::new();
+    for seg in pattern {
+        let (PatternSeg::Param(name, _) | PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name)) = seg else { continue };
+        let arg = snake_case(name);
+        if let Some((other, _)) = seen.iter().find(|(_, a)| *a == arg) {
+            errors.push(format!("parameters `{}` and `{}` both become `{}` in the URL builder", other, name, arg));
+        }
+        seen.push((name, arg));
+    }
+    errors
+}
+
+const RUST_KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+    "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+    "true", "type", "unsafe", "use", "where", "while",
+];
+
+/// Keywords get a trailing `_` (`static` -> `static_`)
+fn rust_ident(name: String) -> String {
+    if RUST_KEYWORDS.contains(&name.as_str()) { format!("{}_", name) } else { name }
+}
+
+fn find_url_builder_collisions(routes: &[RouteFile]) -> Vec<String> {
+    let names: Vec<String> = routes.iter().map(|r| url_builder_name(&route_pattern(&r.route))).collect();
+    let mut errors = Vec::new();
+    for (i, a) in routes.iter().enumerate() {
+        for (j, b) in routes.iter().enumerate().skip(i + 1) {
+            if names[i] == names[j] {
+                errors.push(format!(
+                    "routes {} and {} both generate the URL builder `routes::{}`\n    {}\n    {}",
+                    a.route, b.route, names[i], a.path.display(), b.path.display()
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Emit `pub mod routes` with one URL builder per route. Params are percent-encoded;
+/// typed params take their declared type, the others anything `Display`, and
+/// catch-alls a slice of segments.
+fn emit_url_builders(out: &mut fs::File, routes: &[RouteFile]) {
+    writeln!(out, "\n/// URL builders for every route, e.g. `routes::posts_id_comments_commentId(1, 2)` -> `/posts/1/comments/2`").unwrap();
+    writeln!(out, "#[allow(dead_code, non_snake_case)]").unwrap();
+    writeln!(out, "pub mod routes {{").unwrap();
+    writeln!(out, "    use std::fmt::{{Display, Write}};").unwrap();
+    writeln!(out, "    use ::rsf::__private::percent_encoding::{{utf8_percent_encode, AsciiSet, CONTROLS}};\n").unwrap();
+    writeln!(out, "    /// Characters escaped in a path segment (RFC 3986 `pchar`, plus `%`)").unwrap();
+    writeln!(out, "    const SEGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'\"').add(b'#').add(b'%').add(b'/').add(b'<').add(b'>').add(b'?').add(b'[').add(b'\\\\').add(b']').add(b'^').add(b'`').add(b'{{').add(b'|').add(b'}}');\n").unwrap();
+    writeln!(out, "    fn push(url: &mut String, segment: impl Display) {{").unwrap();
+    writeln!(out, "        let _ = write!(url, \"/{{}}\", utf8_percent_encode(&segment.to_string(), SEGMENT));").unwrap();
+    writeln!(out, "    }}").unwrap();
+    for route_file in routes {
+        let pattern = route_pattern(&route_file.route);
+        let mut args = Vec::new();
+        // `init` is the static prefix the URL starts with, `body` appends the rest
+        let mut init = None;
+        let mut body = Vec::new();
+        let mut literal = String::new();
+        for seg in &pattern {
+            let (arg, ty) = match seg {
+                PatternSeg::Static(s) if s.chars().all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c)) => {
+                    literal.push('/');
+                    literal.push_str(s);
+                    continue;
+                }
+                PatternSeg::Static(s) => {
+                    if init.is_none() {
+                        init = Some(std::mem::take(&mut literal));
+                    }
+                    if !literal.is_empty() {
+                        body.push(format!("url.push_str({:?});", std::mem::take(&mut literal)));
+                    }
+                    body.push(format!("push(&mut url, {:?});", s));
+                    continue;
+                }
+                PatternSeg::Param(name, spec) => {
+                    let ty = match spec {
+                        Some(ParamSpec::Type(ty)) => ty.to_string(),
+                        _ => "impl Display".to_string(),
+                    };
+                    (snake_case(name), ty)
+                }
+                PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name) => (snake_case(name), "&[impl Display]".to_string()),
+            };
+            if init.is_none() {
+                init = Some(std::mem::take(&mut literal));
+            } else if !literal.is_empty() {
+                body.push(format!("url.push_str({:?});", std::mem::take(&mut literal)));
+            }
+            body.push(match seg {
+                PatternSeg::CatchAll(_) | PatternSeg::OptionalCatchAll(_) => format!("{}.iter().for_each(|s| push(&mut url, s));", arg),
+                _ => format!("push(&mut url, {});", arg),
+            });
+            args.push(format!("{}: {}", arg, ty));
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "    /// `{}` ({})", route_file.route, route_file.rel_path).unwrap();
+        writeln!(out, "    pub fn {}({}) -> String {{", url_builder_name(&pattern), args.join(", ")).unwrap();
+        match init {
+            None => {
+                let url = if literal.is_empty() { "/".to_string() } else { literal };
+                writeln!(out, "        String::from({:?})", url).unwrap();
+            }
+            Some(init) => {
+                if !literal.is_empty() {
+                    body.push(format!("url.push_str({:?});", literal));
+                }
+                writeln!(out, "        let mut url = String::from({:?});", init).unwrap();
+                for line in &body {
+                    writeln!(out, "        {}", line).unwrap();
+                }
+                // `/[[...slug]]` with no segments is the root
+                if pattern.len() == 1 && matches!(pattern[0], PatternSeg::OptionalCatchAll(_)) {
+                    writeln!(out, "        if url.is_empty() {{").unwrap();
+                    writeln!(out, "            url.push('/');").unwrap();
+                    writeln!(out, "        }}").unwrap();
+                }
+                writeln!(out, "        url").unwrap();
+            }
+        }
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+/// JSON manifest of every route, sorted by pattern. File paths are relative to the route
+/// root, so the output only changes when the routes do.
+fn route_manifest(routes: &[RouteFile], middleware: &[MiddlewareFile], error_pages: &[ErrorPageFile], mounts: &[Mount]) -> String {
+    let mut entries: Vec<(&String, serde_json::Value)> = routes.iter().map(|route_file| {
+        let pattern = route_pattern(&route_file.route);
+        let scope = route_file.scope(mounts);
+        let mount = &mounts[route_file.mount];
+        let params: Vec<serde_json::Value> = pattern.iter().filter_map(|seg| {
+            let (name, kind, spec) = match seg {
+                PatternSeg::Static(_) => return None,
+                PatternSeg::Param(name, spec) => (name, "param", *spec),
+                PatternSeg::CatchAll(name) => (name, "catch_all", None),
+                PatternSeg::OptionalCatchAll(name) => (name, "optional_catch_all", None),
+            };
+            let mut param = serde_json::json!({ "name": name, "kind": kind });
+            match spec {
+                Some(ParamSpec::Type(ty)) => param["type"] = ty.into(),
+                Some(ParamSpec::Regex(re)) => param["regex"] = re.into(),
+                None => {}
+            }
+            Some(param)
+        }).collect();
+        // `METHODS` order rather than declaration order
+        let methods: Vec<&str> = METHODS.iter().copied().filter(|m| route_file.handlers.iter().any(|h| h.method == *m)).collect();
+        let mut chain: Vec<&MiddlewareFile> = middleware.iter().filter(|mw| mw.applies_to(route_file)).collect();
+        chain.sort_by_key(|mw| mw.dir.len());
+        let mut pages: Vec<&ErrorPageFile> = error_pages.iter().filter(|p| scope.starts_with(&p.scope)).collect();
+        pages.sort_by_key(|p| std::cmp::Reverse(p.scope.len()));
+        (&route_file.route, serde_json::json!({
+            "pattern": route_file.route,
+            "methods": methods,
+            "file": route_file.rel_path,
+            "mount": { "dir": mount.config_dir, "prefix": format!("/{}", mount.prefix.join("/")) },
+            "type": route_file.config.kind,
+            "config": route_file.config.to_json(),
+            "params": params,
+            "groups": route_file.groups,
+            "middleware": chain.iter().map(|mw| &mw.rel_path).collect::<Vec<_>>(),
+            "error_pages": pages.iter().map(|p| &p.rel_path).collect::<Vec<_>>(),
+            "url_builder": url_builder_name(&pattern),
+        }))
+    }).collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    let manifest = serde_json::json!({
+        "version": 1,
+        "routes": entries.into_iter().map(|(_, e)| e).collect::<Vec<_>>(),
+    });
+    serde_json::to_string_pretty(&manifest).unwrap() + "\n"
+}
+
+/// File name of per-directory middleware
+const MIDDLEWARE_FILE: &str = "_middleware.rs";
+
+/// A `_middleware.rs` file and the hooks it defines
+struct MiddlewareFile {
+    path: PathBuf,
+    /// Path relative to its mount's directory
+    rel_path: String,
+    /// Index of the mount it belongs to; middleware only wraps routes of its own tree
+    mount: usize,
+    /// Directory it applies to, relative to the mount's directory, e.g. ["api", "admin"]
+    dir: Vec<String>,
+    /// `Some(is_async)` when `before` is defined
+    before: Option<bool>,
+    /// `Some(is_async)` when `after` is defined
+    after: Option<bool>,
+}
+
+impl MiddlewareFile {
+    fn applies_to(&self, route_file: &RouteFile) -> bool {
+        self.mount == route_file.mount && route_file.dir.starts_with(&self.dir)
+    }
+}
+
+/// Directory components of a file relative to the route root (route groups included).
+fn relative_dir(path: &Path, root: &Path) -> Vec<String> {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.parent()
+        .map(|p| p.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect())
+        .unwrap_or_default()
+}
+
+/// Parse a `_middleware.rs` file and check its `before`/`after` hook signatures.
+fn analyze_middleware_file(path: &Path, mount_index: usize, mount: &Mount) -> Result<MiddlewareFile, Vec<String>> {
+    use syn::spanned::Spanned;
+
+    let content = fs::read_to_string(path).map_err(|e| vec![format!("{}: failed to read middleware: {}", path.display(), e)])?;
+    let ast = syn::parse_file(&content)
+        .map_err(|e| vec![diagnostic(path, e.span(), &format!("failed to parse middleware file: {}", e))])?;
+
+    let mut mw = MiddlewareFile {
+        path: path.to_path_buf(),
+        rel_path: mount.rel_path(path),
+        mount: mount_index,
+        dir: relative_dir(path, &mount.dir),
+        before: None,
+        after: None,
+    };
+    let mut errors = Vec::new();
+    for item in &ast.items {
+        let syn::Item::Fn(func) = item else { continue };
+        let sig = &func.sig;
+        let is_async = sig.asyncness.is_some();
+        let args: Vec<&syn::Type> = sig.inputs.iter().filter_map(|a| match a {
+            syn::FnArg::Typed(t) => Some(&*t.ty),
+            syn::FnArg::Receiver(_) => None,
+        }).collect();
+        let takes_request = |ty: &syn::Type| matches!(ty, syn::Type::Reference(r) if r.mutability.is_none() && type_is(&r.elem, "Request"));
+        let returns = |name: &str| matches!(&sig.output, syn::ReturnType::Type(_, ty) if type_is(ty, name));
+        match sig.ident.to_string().as_str() {
+            "before" | "after" if !is_exported(&func.vis) => {
+                errors.push(not_exported(path, sig.ident.span(), &sig.ident.to_string()));
+            }
+            "before" => {
+                if sig.inputs.len() == 1 && args.len() == 1 && takes_request(args[0]) && returns("Option") {
+                    mw.before = Some(is_async);
+                } else {
+                    errors.push(diagnostic(path, sig.span(), "expected `fn before(req: &Request) -> Option<Response>`"));
+                }
+            }
+            "after" => {
+                if sig.inputs.len() == 2 && args.len() == 2 && takes_request(args[0]) && type_is(args[1], "Response") && returns("Response") {
+                    mw.after = Some(is_async);
+                } else {
+                    errors.push(diagnostic(path, sig.span(), "expected `fn after(req: &Request, res: Response) -> Response`"));
+                }
+            }
+            _ => {}
+        }
+    }
+    if errors.is_empty() && mw.before.is_none() && mw.after.is_none() {
+        errors.push(format!("{}: middleware defines neither `before` nor `after`", path.display()));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(mw)
+}
+
+const STATE_FILE: &str = "_state.rs";
+
+/// The `_state.rs` file that builds the app state
+struct StateFile {
+    path: PathBuf,
+    /// Path relative to its mount's directory
+    rel_path: String,
+    mount: usize,
+}
+
+/// Parse `_state.rs` and check its `state` signature.
+fn analyze_state_file(path: &Path, mount_index: usize, mount: &Mount) -> Result<StateFile, Vec<String>> {
+    use syn::spanned::Spanned;
+
+    let content = fs::read_to_string(path).map_err(|e| vec![format!("{}: failed to read app state: {}", path.display(), e)])?;
+    let ast = syn::parse_file(&content)
+        .map_err(|e| vec![diagnostic(path, e.span(), &format!("failed to parse app state file: {}", e))])?;
+    // The state is shared by every route, so it doesn't belong to a directory
+    if !relative_dir(path, &mount.dir).is_empty() {
+        return Err(vec![format!("{}: `_state.rs` must be at the root of a route tree", path.display())]);
+    }
+
+    let state = ast.items.iter().find_map(|item| match item {
+        syn::Item::Fn(func) if func.sig.ident == "state" => Some(func),
+        _ => None,
+    });
+    let Some(state) = state else {
+        return Err(vec![format!("{}: `_state.rs` must define `fn state() -> AppState`", path.display())]);
+    };
+    let sig = &state.sig;
+    let valid = sig.generics.params.is_empty()
+        && sig.inputs.is_empty()
+        && sig.asyncness.is_none()
+        && matches!(&sig.output, syn::ReturnType::Type(_, ty) if type_is(ty, "AppState"));
+    if !valid {
+        return Err(vec![diagnostic(path, sig.span(), "expected `fn state() -> AppState`")]);
+    }
+    if !is_exported(&state.vis) {
+        return Err(vec![not_exported(path, sig.ident.span(), "state")]);
+    }
+    Ok(StateFile { path: path.to_path_buf(), rel_path: mount.rel_path(path), mount: mount_index })
+}
+
+/// Which error an error page renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+    /// `_404.rs`
+    NotFound,
+    /// `_405.rs`
+    MethodNotAllowed,
+    /// `_error.rs` - any error without a more specific page
+    Error,
+}
+
+impl ErrorKind {
+    fn from_file_name(name: &str) -> Option<Self> {
+        match name {
+            "_404.rs" => Some(ErrorKind::NotFound),
+            "_405.rs" => Some(ErrorKind::MethodNotAllowed),
+            "_error.rs" => Some(ErrorKind::Error),
+            _ => None,
+        }
+    }
+}
+
+/// A `_404.rs`, `_405.rs` or `_error.rs` file
+struct ErrorPageFile {
+    path: PathBuf,
+    /// Path relative to its mount's directory
+    rel_path: String,
+    /// Index of the mount it was found in
+    mount: usize,
+    kind: ErrorKind,
+    /// URL prefix it applies to: the mount prefix, then its directory (route groups included).
+    /// Unlike middleware, pages also cover other trees mounted below them.
+    scope: Vec<String>,
+    is_async: bool,
+}
+
+/// Parse an error page and check its `render` signature.
+fn analyze_error_page(path: &Path, mount_index: usize, mount: &Mount, kind: ErrorKind) -> Result<ErrorPageFile, Vec<String>> {
+    use syn::spanned::Spanned;
+
+    let content = fs::read_to_string(path).map_err(|e| vec![format!("{}: failed to read error page: {}", path.display(), e)])?;
+    let ast = syn::parse_file(&content)
+        .map_err(|e| vec![diagnostic(path, e.span(), &format!("failed to parse error page: {}", e))])?;
+
+    let render = ast.items.iter().find_map(|item| match item {
+        syn::Item::Fn(func) if func.sig.ident == "render" => Some(func),
+        _ => None,
+    });
+    let Some(render) = render else {
+        return Err(vec![format!("{}: error page must define `fn render(req: &Request, err: &FrameworkError) -> Response`", path.display())]);
+    };
+    let sig = &render.sig;
+    let args: Vec<&syn::Type> = sig.inputs.iter().filter_map(|a| match a {
+        syn::FnArg::Typed(t) => Some(&*t.ty),
+        syn::FnArg::Receiver(_) => None,
+    }).collect();
+    let is_ref_to = |ty: &syn::Type, name: &str| matches!(ty, syn::Type::Reference(r) if r.mutability.is_none() && type_is(&r.elem, name));
+    let valid = sig.generics.params.is_empty()
+        && sig.inputs.len() == 2
+        && args.len() == 2
+        && is_ref_to(args[0], "Request")
+        && is_ref_to(args[1], "FrameworkError")
+        && matches!(&sig.output, syn::ReturnType::Type(_, ty) if type_is(ty, "Response"));
+    if !valid {
+        return Err(vec![diagnostic(path, sig.span(), "expected `fn render(req: &Request, err: &FrameworkError) -> Response`")]);
+    }
+    if !is_exported(&render.vis) {
+        return Err(vec![not_exported(path, sig.ident.span(), "render")]);
+    }
+
+    let scope = mount.prefix.iter().cloned().chain(relative_dir(path, &mount.dir)).collect();
+    Ok(ErrorPageFile { path: path.to_path_buf(), rel_path: mount.rel_path(path), mount: mount_index, kind, scope, is_async: sig.asyncness.is_some() })
+}
+
+/// `ErrorPages { .. }` expression for a scope: per kind, the page in the deepest enclosing directory
+/// that has either that page or an `_error.rs`.
+fn resolve_error_pages(pages: &[ErrorPageFile], scope: &[String]) -> String {
+    // the nearest directory wins: a closer `_error.rs` beats a `_404.rs` further up, while
+    // in the same directory the specific page beats `_error.rs`
+    let nearest = |kind: ErrorKind| {
+        pages.iter().enumerate()
+            .filter(|(_, p)| (p.kind == kind || p.kind == ErrorKind::Error) && scope.starts_with(&p.scope))
+            .max_by_key(|(_, p)| (p.scope.len(), p.kind == kind))
+            .map_or("None".to_string(), |(i, _)| format!("Some(ERROR_PAGE_{})", i))
+    };
+    format!(
+        "ErrorPages {{ not_found: {}, method_not_allowed: {}, error: {} }}",
+        nearest(ErrorKind::NotFound),
+        nearest(ErrorKind::MethodNotAllowed),
+        nearest(ErrorKind::Error)
+    )
+}
+
+/// Parse a route file and classify every top-level method handler in it.
+/// Returns one `file:line:col: message` diagnostic per unsupported item.
+fn analyze_route_file(path: &Path, mount_index: usize, mount: &Mount) -> Result<RouteFile, Vec<String>> {
+    let rel_path = mount.rel_path(path);
+    let route = mount.route(&path_to_route(&rel_path));
+    let groups = route_groups(&rel_path);
+    let pattern = route_pattern(&route);
+    let mut pattern_errors = validate_pattern(&pattern);
+    if pattern_errors.is_empty() {
+        pattern_errors = builder_arg_collisions(&pattern);
+    }
+    if !pattern_errors.is_empty() {
+        return Err(pattern_errors.into_iter().map(|msg| format!("{}: {} in route {}", path.display(), msg, route)).collect());
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| vec![format!("{}: failed to read route file: {}", path.display(), e)])?;
+
+    let ast = match syn::parse_file(&content) {
+        Ok(ast) => ast,
+        Err(e) => return Err(vec![diagnostic(path, e.span(), &format!("failed to parse route file: {}", e))]),
+    };
+
+    let mut handlers: Vec<HandlerSig> = Vec::new();
+    let mut errors = Vec::new();
+    for item in &ast.items {
+        let syn::Item::Fn(func) = item else { continue };
+        let fn_name = func.sig.ident.to_string();
+        let Some(method) = METHODS.iter().copied().find(|m| *m == fn_name || m.to_lowercase() == fn_name) else {
+            continue;
+        };
+
+        if handlers.iter().any(|h| h.method == method) {
+            errors.push(diagnostic(path, func.sig.ident.span(), &format!("duplicate handler for {}", method)));
+            continue;
+        }
+        if !is_exported(&func.vis) {
+            errors.push(not_exported(path, func.sig.ident.span(), &fn_name));
+            continue;
+        }
+        // The wrappers name the handler's argument and return types, so the file's own types in
+        // the signature must be visible to them too
+        for ty in private_types_in(&func.sig, &ast) {
+            errors.push(diagnostic(path, ty.span(), &format!("`{}` appears in the signature of `{}`, so it must be `pub`", ty, fn_name)));
+        }
+        match classify_handler(&func.sig, &pattern) {
+            Ok((inputs, output)) => handlers.push(HandlerSig {
+                method,
+                fn_name,
+                is_async: func.sig.asyncness.is_some(),
+                inputs,
+                output,
+            }),
+            Err((span, msg)) => errors.push(diagnostic(path, span, &format!("unsupported {} handler: {}", method, msg))),
+        }
+    }
+
+    let config = match route_config::read(&ast) {
+        Ok(config) => config,
+        Err(errs) => {
+            errors.extend(errs.into_iter().map(|(span, msg)| diagnostic(path, span, &msg)));
+            route_config::RouteMeta::default()
+        }
+    };
+    // Settings that could never take effect for the file's handlers
+    let route_item = ast.items.iter().find_map(|item| match item {
+        syn::Item::Const(c) if c.ident == "ROUTE" => Some(c),
+        _ => None,
+    });
+    if let Some(route_item) = route_item {
+        let span = route_item.ident.span();
+        if !is_exported(&route_item.vis) {
+            errors.push(not_exported(path, span, "ROUTE"));
+        }
+        if config.timeout_ms.is_some() && !handlers.iter().any(|h| h.is_async) {
+            errors.push(diagnostic(path, span, "`timeout` only applies to async handlers, and this file has none"));
+        }
+        if config.cache_ttl_ms.is_some() && !handlers.iter().any(|h| h.method == "GET") {
+            errors.push(diagnostic(path, span, "`cache_ttl` only applies to GET responses, and this file has no GET handler"));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(RouteFile {
+            path: path.to_path_buf(),
+            dir: relative_dir(path, &mount.dir),
+            rel_path,
+            mount: mount_index,
+            route,
+            groups,
+            config,
+            content,
+            handlers,
+        })
+    } else {
+        Err(errors)
+    }
+}
+
+/// Work out how to call a handler from its signature.
+fn classify_handler(sig: &syn::Signature, pattern: &[PatternSeg]) -> Result<(Vec<HandlerArg>, HandlerOutput), (proc_macro2::Span, String)> {
+    use syn::spanned::Spanned;
+
+    if !sig.generics.params.is_empty() {
+        return Err((sig.generics.span(), "handlers cannot be generic".to_string()));
+    }
+
+    let mut inputs = Vec::new();
+    for arg in &sig.inputs {
+        let syn::FnArg::Typed(arg) = arg else {
+            return Err((arg.span(), "handlers cannot take `self`".to_string()));
+        };
+        let input = match &*arg.ty {
+            syn::Type::Reference(r) if r.mutability.is_none() && type_is(&r.elem, "Request") => HandlerArg::Request,
+            syn::Type::Reference(r) if r.mutability.is_none() && type_is(&r.elem, "HashMap") => HandlerArg::ParamsRef,
+            ty if type_is(ty, "HashMap") => HandlerArg::ParamsOwned,
+            ty => {
+                // A plain value must be a path param taken by name with the type of its
+                // segment; any other type is an extractor, which rustc checks implements
+                // `FromRequest`
+                let name = binding_name(&arg.pat);
+                let expected = pattern.iter().find_map(|seg| match seg {
+                    PatternSeg::Param(n, spec) if *n == name => Some(spec.map_or("String", |s| s.rust_type())),
+                    PatternSeg::CatchAll(n) | PatternSeg::OptionalCatchAll(n) if *n == name => Some("String"),
+                    _ => None,
+                });
+                let plain = matches!(ty, syn::Type::Reference(_)) || PLAIN_TYPES.iter().any(|t| type_is(ty, t));
+                match expected {
+                    Some(expected) if type_is(ty, expected) => HandlerArg::Path { name, ty: expected.to_string() },
+                    Some(expected) if plain => {
+                        return Err((ty.span(), format!("path parameter `{}` has type `{}`", name, expected)));
+                    }
+                    None if plain => {
+                        return Err((arg.span(), "expected a `&Request`, `&HashMap<String, String>` or `HashMap<String, String>` parameter, a path parameter by name or an extractor such as `Json<T>`".to_string()));
+                    }
+                    _ => HandlerArg::Extract { name },
+                }
+            }
+        };
+        if inputs.contains(&input) && !matches!(input, HandlerArg::Extract { .. }) {
+            return Err((arg.span(), "duplicate handler parameter".to_string()));
+        }
+        inputs.push(input);
+    }
+
+    // rustc checks that the return type implements `IntoResponse`
+    let output = match &sig.output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            ty if type_is(ty, "String") => HandlerOutput::Text,
+            syn::Type::Reference(r) if type_is(&r.elem, "str") => HandlerOutput::Text,
+            ty if type_is(ty, "Response") => HandlerOutput::Response,
+            ty if returns_framework_error(ty) => HandlerOutput::Fallible,
+            syn::Type::Tuple(t) if t.elems.len() == 2 && type_is(&t.elems[1], "u16") => HandlerOutput::TextWithStatus,
+            syn::Type::Tuple(t) if t.elems.is_empty() => {
+                return Err((ty.span(), "handlers must return a value that implements `IntoResponse`".to_string()));
+            }
+            _ => HandlerOutput::Other,
+        },
+        syn::ReturnType::Default => {
+            return Err((sig.ident.span(), "handlers must return a value that implements `IntoResponse`".to_string()));
+        }
+    };
+
+    Ok((inputs, output))
+}
+
+/// `Result<T, FrameworkError>`, or `Result<T>` (`rsf::Result`, whose error is a `FrameworkError`)
+fn returns_framework_error(ty: &syn::Type) -> bool {
+    let syn::Type::Path(p) = ty else { return false };
+    let Some(last) = p.path.segments.last().filter(|s| s.ident == "Result") else { return false };
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else { return false };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [syn::GenericArgument::Type(_)] => true,
+        [syn::GenericArgument::Type(_), syn::GenericArgument::Type(err)] => type_is(err, "FrameworkError"),
+        _ => false,
+    }
+}
+
+/// Types a handler argument can only have as a path param (anything else is an extractor)
+const PLAIN_TYPES: [&str; 18] = [
+    "String", "str", "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
+/// The name an argument is bound to: `id` in `id: u64` and in `Path(id): Path<u64>`, empty
+/// for `_` and other patterns
+fn binding_name(pat: &syn::Pat) -> String {
+    match pat {
+        syn::Pat::Ident(p) => p.ident.to_string(),
+        syn::Pat::TupleStruct(p) if p.elems.len() == 1 => binding_name(&p.elems[0]),
+        _ => String::new(),
+    }
+}
+
+/// True when `ty` is a path type whose last segment is `name` (e.g. `core::engine::Request`).
+fn type_is(ty: &syn::Type, name: &str) -> bool {
+    match ty {
+        syn::Type::Path(p) => p.qself.is_none() && p.path.segments.last().map(|s| s.ident == name).unwrap_or(false),
+        syn::Type::Paren(p) => type_is(&p.elem, name),
+        syn::Type::Group(g) => type_is(&g.elem, name),
+        _ => false,
+    }
+}
+
+fn diagnostic(path: &Path, span: proc_macro2::Span, msg: &str) -> String {
+    let start = span.start();
+    format!("{}:{}:{}: {}", path.display(), start.line, start.column + 1, msg)
+}
+
+fn path_to_route(path: &str) -> String {
+    // Convert "auth/main.rs" -> "/auth/main"; index.rs -> "/auth" or "/"
+    // Route groups don't contribute to the URL: "(admin)/users.rs" -> "/users"
+    let p = path.replace('\\', "/");
+    let p = p.trim_end_matches(".rs");
+    let mut segs: Vec<&str> = p.split('/').filter(|s| !s.is_empty() && !is_route_group(s)).collect();
+    if segs.last() == Some(&"index") {
+        segs.pop();
+    }
+    format!("/{}", segs.join("/"))
+}
+
+/// Names of the `(group)` folders a route file lives in, outermost first.
+fn route_groups(path: &str) -> Vec<String> {
+    path.replace('\\', "/")
+        .split('/')
+        .filter(|s| is_route_group(s))
+        .map(|s| s[1..s.len() - 1].to_string())
+        .collect()
+}
+
+fn is_route_group(seg: &str) -> bool {
+    seg.len() > 2 && seg.starts_with('(') && seg.ends_with(')')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub(crate) const GET: &str = "pub fn get() -> String { String::new() }";
+
+    /// Analyze `source` as the route file `rel_path` of a scratch route tree
+    pub(crate) fn route_file(rel_path: &str, source: impl AsRef<[u8]>) -> Result<RouteFile, Vec<String>> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!("rsf-build-{}-{}", std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed)));
+        let path = dir.join(rel_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, source).unwrap();
+        let result = analyze_route_file(&path, 0, &Mount { dir: dir.clone(), config_dir: "routes".to_string(), prefix: Vec::new() });
+        fs::remove_dir_all(&dir).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_route_conflicts() {
+        let routes: Vec<RouteFile> = ["users/[id].rs", "users/[name].rs", "users/me.rs", "(admin)/users/me.rs", "orders/[id:u64].rs", "orders/[code:regex(x[0-9]+)].rs"]
+            .iter()
+            .map(|path| route_file(path, GET).unwrap())
+            .collect();
+        let errors = find_route_conflicts(&routes);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].starts_with("ambiguous routes /users/[id] and /users/[name]:\n"), "{}", errors[0]);
+        assert!(errors[1].starts_with("duplicate route /users/me:\n"), "{}", errors[1]);
+        assert!(errors[1].ends_with("(admin)/users/me.rs"), "{}", errors[1]);
+    }
+
+    #[test]
+    fn test_url_builders() {
+        let name = |route: &str| url_builder_name(&route_pattern(route));
+        assert_eq!(name("/"), "index");
+        assert_eq!(name("/posts/[id]/comments/[commentId]"), "posts_id_comments_commentId");
+        assert_eq!(name("/2024/a-b.c"), "_2024_a_b_c");
+        assert_eq!(name("/static"), "static_");
+        assert_eq!(snake_case("commentId"), "comment_id");
+        assert_eq!(snake_case("type"), "type_");
+
+        let routes: Vec<RouteFile> = ["a-b.rs", "a_b.rs"].iter().map(|path| route_file(path, GET).unwrap()).collect();
+        let errors = find_url_builder_collisions(&routes);
+        assert!(errors[0].starts_with("routes /a-b and /a_b both generate the URL builder `routes::a_b`"), "{}", errors[0]);
+        let Err(errors) = route_file("a/[userId]/[user_id].rs", GET) else { panic!("colliding builder args") };
+        assert!(errors[0].ends_with("parameters `userId` and `user_id` both become `user_id` in the URL builder in route /a/[userId]/[user_id]"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_analyze_route_file() {
+        let users = route_file("users/[id:u64].rs", "pub fn GET(req: &Request, id: u64) -> String { String::new() }\npub async fn delete() -> Response { todo!() }").unwrap();
+        assert_eq!(users.route, "/users/[id:u64]");
+        let methods: Vec<(&str, bool, &HandlerOutput)> = users.handlers.iter().map(|h| (h.method, h.is_async, &h.output)).collect();
+        assert_eq!(methods, [("GET", false, &HandlerOutput::Text), ("DELETE", true, &HandlerOutput::Response)]);
+
+        let Err(errors) = route_file("users/[id:u64].rs", "pub fn get(id: String) -> String { id }\nfn post() -> String { String::new() }\npub fn put<T>() -> T { todo!() }") else {
+            panic!("invalid handlers")
+        };
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("path parameter `id` has type `u64`"), "{}", errors[0]);
+        assert!(errors[1].contains("`post`"), "{}", errors[1]);
+        assert!(errors[2].contains("handlers cannot be generic"), "{}", errors[2]);
+
+        let Err(errors) = route_file("bad.rs", b"pub fn get() -> String { \xff }") else { panic!("invalid UTF-8") };
+        assert!(errors[0].contains("failed to read route file"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_trie_precedence() {
+        let mut tree = TrieNode::default();
+        for (id, route) in ["/p/[name]", "/p/[code:regex(x[0-9]+)]", "/p/[id:u64]", "/p/new", "/p/[...rest]"].iter().enumerate() {
+            tree.insert(&route_pattern(route), id);
+        }
+        let p = &tree.statics["p"];
+        assert_eq!(p.statics["new"].leaf, Some(3));
+        let order: Vec<(Option<ParamSpec>, Option<usize>)> = p.params.iter().map(|(spec, child)| (*spec, child.leaf)).collect();
+        assert_eq!(order, [
+            (Some(ParamSpec::Type("u64")), Some(2)),
+            (Some(ParamSpec::Regex("x[0-9]+")), Some(1)),
+            (None, Some(0)),
+        ]);
+        assert_eq!(p.catch_all, Some(4));
+        assert_eq!(p.leaf, None);
+    }
+}
@@ -0,0 +1,830 @@
+// OpenAPI 3.1 document for the route tree, built from the route files the router is generated from.
+//
+// Paths and params come from file names. Request and response schemas come from the serde
+// types a handler takes (`Json<T>`, `Query<T>`, ... arguments), reads in its body
+// (`req.json::<T>()`, `serde_json::from_str::<T>(..)`), returns (`Json<T>`) and writes
+// (`serde_json::to_string(&value)`, `ResponseBuilder::json(&value)`), and status codes from
+// `(body, 201)` tuples and `.status(404)` calls. Only types defined in the route file itself
+// can be described; anything else is left open (`{}`).
+
+use super::route_config::RouteMeta;
+use super::{HandlerOutput, HandlerSig, RouteFile, url_builder_name};
+use rsf_pattern::{route_pattern, ParamSpec, PatternSeg};
+use serde_json::{json, Map, Value};
+use syn::visit::Visit;
+
+/// Render the document for every `RouteKind::Api` route.
+pub fn document(routes: &[RouteFile], title: &str, version: &str) -> String {
+    let mut paths = Map::new();
+    let mut components = Components::default();
+    let mut security_schemes = Map::new();
+    for route_file in routes.iter().filter(|r| r.config.kind == "api") {
+        // Route files already parsed once; a failure here would have aborted the build
+        let Ok(ast) = syn::parse_file(&route_file.content) else { continue };
+        let pattern = route_pattern(&route_file.route);
+        let types = LocalTypes::collect(&ast);
+
+        let mut operations = Map::new();
+        for h in &route_file.handlers {
+            let Some(func) = ast.items.iter().find_map(|item| match item {
+                syn::Item::Fn(f) if f.sig.ident == h.fn_name => Some(f),
+                _ => None,
+            }) else { continue };
+            let mut body = BodyFacts::default();
+            body.visit_block(&func.block);
+            let mut op = operation(h, func, &pattern, &body, &types, &mut components, &route_file.rel_path);
+            apply_route_config(&mut op, &route_file.config, &mut security_schemes);
+            operations.insert(h.method.to_lowercase(), op);
+        }
+
+        let parameters: Vec<Value> = pattern.iter().filter_map(path_parameter).collect();
+        let mut item = operations;
+        if !parameters.is_empty() {
+            item.insert("parameters".to_string(), Value::Array(parameters));
+        }
+        let openapi_paths = openapi_paths(&pattern);
+        for (i, path) in openapi_paths.iter().enumerate() {
+            let mut item = item.clone();
+            // An optional catch-all also matches the bare prefix, which has no `{slug}` param
+            // and gets operation ids of its own
+            if i == 0 && openapi_paths.len() == 2 {
+                item.remove("parameters");
+                let prefix = url_builder_name(&pattern[..pattern.len() - 1]);
+                for (method, op) in item.iter_mut() {
+                    op["operationId"] = format!("{}_{}", method, prefix).into();
+                }
+            }
+            paths.insert(path.clone(), Value::Object(item));
+        }
+    }
+
+    let mut doc = json!({
+        "openapi": "3.1.0",
+        "info": { "title": title, "version": version },
+        "paths": paths,
+    });
+    if !components.schemas.is_empty() {
+        doc["components"]["schemas"] = Value::Object(components.schemas);
+    }
+    if !security_schemes.is_empty() {
+        doc["components"]["securitySchemes"] = Value::Object(security_schemes);
+    }
+    serde_json::to_string_pretty(&doc).unwrap() + "\n"
+}
+
+/// `/posts/[id]/comments/[commentId]` -> `/posts/{id}/comments/{commentId}`; an optional
+/// catch-all gives both the bare prefix and the `{slug}` path.
+fn openapi_paths(pattern: &[PatternSeg]) -> Vec<String> {
+    let mut path = String::new();
+    for seg in pattern {
+        match seg {
+            PatternSeg::Static(s) => path.push_str(&format!("/{}", s)),
+            PatternSeg::Param(name, _) | PatternSeg::CatchAll(name) => path.push_str(&format!("/{{{}}}", name)),
+            PatternSeg::OptionalCatchAll(name) => {
+                let bare = if path.is_empty() { "/".to_string() } else { path.clone() };
+                return vec![bare, format!("{}/{{{}}}", path, name)];
+            }
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    vec![path]
+}
+
+fn path_parameter(seg: &PatternSeg) -> Option<Value> {
+    let (name, schema, description) = match seg {
+        PatternSeg::Static(_) => return None,
+        PatternSeg::Param(name, Some(ParamSpec::Type(ty))) => (name, primitive_schema(ty).unwrap_or_else(|| json!({})), None),
+        PatternSeg::Param(name, Some(ParamSpec::Regex(re))) => (name, json!({ "type": "string", "pattern": format!("^(?:{})$", re) }), None),
+        PatternSeg::Param(name, None) => (name, json!({ "type": "string" }), None),
+        PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name) => {
+            (name, json!({ "type": "string" }), Some("The rest of the path, one or more `/`-separated segments"))
+        }
+    };
+    let mut param = json!({ "name": name, "in": "path", "required": true, "schema": schema });
+    if let Some(description) = description {
+        param["description"] = description.into();
+    }
+    Some(param)
+}
+
+fn operation(
+    h: &HandlerSig,
+    func: &syn::ItemFn,
+    pattern: &[PatternSeg],
+    body: &BodyFacts,
+    types: &LocalTypes,
+    components: &mut Components,
+    file: &str,
+) -> Value {
+    let mut op = Map::new();
+    op.insert("operationId".to_string(), format!("{}_{}", h.method.to_lowercase(), url_builder_name(pattern)).into());
+    if let Some(summary) = doc_summary(&func.attrs) {
+        op.insert("summary".to_string(), summary.into());
+    }
+    op.insert("description".to_string(), format!("Defined in `{}`", file).into());
+
+    // Extractor arguments: `Json<T>` / `Form<T>` describe the body, `Query<T>` / `Header<T>` /
+    // `Cookie<T>` add parameters, and the statuses they reject requests with are documented
+    // unless the argument is an `Option<..>`
+    let mut request = body.request.clone().map(|ty| ("application/json", ty, true));
+    let mut parameters = Vec::new();
+    let mut rejections: Vec<u16> = Vec::new();
+    for arg in &func.sig.inputs {
+        let syn::FnArg::Typed(arg) = arg else { continue };
+        let (ty, required) = match option_inner(&arg.ty) {
+            Some(inner) => (inner, false),
+            None => ((*arg.ty).clone(), true),
+        };
+        let syn::Type::Path(p) = &ty else { continue };
+        let Some(last) = p.path.segments.last() else { continue };
+        let Some(inner) = generic_type(&last.arguments) else { continue };
+        let statuses: &[u16] = match last.ident.to_string().as_str() {
+            "Json" => {
+                request = Some(("application/json", inner, required));
+                &[400, 415, 422]
+            }
+            "Form" => {
+                request = Some(("application/x-www-form-urlencoded", inner, required));
+                &[400, 415, 422]
+            }
+            "Query" => {
+                parameters.extend(components.query_parameters(&inner, types, required));
+                &[400]
+            }
+            kind @ ("Header" | "Cookie") => {
+                let name = super::binding_name(&arg.pat);
+                let (name, location) = if kind == "Header" { (name.replace('_', "-"), "header") } else { (name, "cookie") };
+                let schema = components.schema(&inner, types).unwrap_or_else(|| json!({}));
+                parameters.push(json!({ "name": name, "in": location, "required": required, "schema": schema }));
+                &[400]
+            }
+            "Path" => &[400],
+            _ => &[],
+        };
+        if required {
+            rejections.extend(statuses);
+        }
+    }
+    if !parameters.is_empty() {
+        op.insert("parameters".to_string(), Value::Array(parameters));
+    }
+    if let Some((media_type, ty, required)) = request && let Some(schema) = components.schema(&ty, types) {
+        op.insert("requestBody".to_string(), json!({
+            "required": required,
+            "content": { media_type: { "schema": schema } },
+        }));
+    }
+
+    // `-> String` handlers always answer 200 with whatever they serialized
+    let mut returns = match h.output {
+        HandlerOutput::Text => vec![(200, body.serialized.clone())],
+        HandlerOutput::TextWithStatus | HandlerOutput::Response | HandlerOutput::Fallible | HandlerOutput::Other => body.responses.clone(),
+    };
+    // `Json<T>` in the return type describes the success body, `Option<..>` adds a 404
+    if let syn::ReturnType::Type(_, ty) = &func.sig.output {
+        if let Some(ty) = returned_json(ty) {
+            match returns.iter_mut().find(|(status, _)| (200..300).contains(status)) {
+                Some(success) => success.1 = Some(ty),
+                None => returns.push((200, Some(ty))),
+            }
+        }
+        if super::type_is(ty, "Option") {
+            returns.push((404, None));
+        }
+    }
+    // A `Response` built some other way (`file.to_response()`) is most likely the success case
+    let has_success = returns.iter().any(|(status, _)| (200..300).contains(status));
+    if returns.is_empty() || (h.output != HandlerOutput::TextWithStatus && !has_success) {
+        returns.push((200, None));
+    }
+    for status in rejections {
+        if !returns.iter().any(|(s, _)| *s == status) {
+            returns.push((status, None));
+        }
+    }
+    returns.sort_by_key(|(status, _)| *status);
+    let mut responses = Map::new();
+    for (status, ty) in returns {
+        let schema = ty.and_then(|ty| components.schema(&ty, types));
+        let resp = responses.entry(status.to_string()).or_insert_with(|| json!({ "description": status_description(status) }));
+        if let Some(schema) = schema && resp.get("content").is_none() {
+            resp["content"] = json!({ "application/json": { "schema": schema } });
+        }
+    }
+    op.insert("responses".to_string(), Value::Object(responses));
+    Value::Object(op)
+}
+
+/// Tags, required credentials and the error responses the route's `ROUTE` settings add
+fn apply_route_config(op: &mut Value, config: &RouteMeta, security_schemes: &mut Map<String, Value>) {
+    if !config.tags.is_empty() {
+        op["tags"] = json!(config.tags);
+    }
+    let scheme = match config.auth {
+        "bearer" => Some(("bearerAuth", json!({ "type": "http", "scheme": "bearer" }))),
+        "basic" => Some(("basicAuth", json!({ "type": "http", "scheme": "basic" }))),
+        _ => None,
+    };
+    if let Some((name, scheme)) = scheme {
+        security_schemes.insert(name.to_string(), scheme);
+        let mut requirement = Map::new();
+        requirement.insert(name.to_string(), json!([]));
+        op["security"] = json!([requirement]);
+    }
+    let statuses = [
+        (config.auth != "none", 401),
+        (config.max_body.is_some(), 413),
+        (config.rate_limit.is_some(), 429),
+        (config.timeout_ms.is_some(), 503),
+    ];
+    for (applies, status) in statuses {
+        if applies && op["responses"].get(status.to_string()).is_none() {
+            op["responses"][status.to_string()] = json!({ "description": status_description(status) });
+        }
+    }
+}
+
+/// `T` of a `Json<T>` return type, also inside `Option<..>`, `Result<.., E>` and `(.., u16)`
+fn returned_json(ty: &syn::Type) -> Option<syn::Type> {
+    match ty {
+        syn::Type::Tuple(t) if t.elems.len() == 2 => returned_json(&t.elems[0]),
+        syn::Type::Paren(p) => returned_json(&p.elem),
+        syn::Type::Path(p) => {
+            let last = p.path.segments.last()?;
+            let syn::PathArguments::AngleBracketed(args) = &last.arguments else { return None };
+            let syn::GenericArgument::Type(inner) = args.args.first()? else { return None };
+            match last.ident.to_string().as_str() {
+                "Json" => Some(inner.clone()),
+                "Option" | "Result" => returned_json(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `T` of an `Option<T>` type
+fn option_inner(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(p) = ty else { return None };
+    let last = p.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    generic_type(&last.arguments)
+}
+
+/// First line of a `///` doc comment
+fn doc_summary(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        syn::Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value().trim().to_string()),
+            _ => None,
+        },
+        _ => None,
+    }).filter(|s| !s.is_empty())
+}
+
+fn status_description(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Response",
+    }
+}
+
+/// What a handler body reads and writes, as far as it can be told from its syntax
+#[derive(Default)]
+struct BodyFacts {
+    /// Type deserialized from the request body
+    request: Option<syn::Type>,
+    /// Status and serialized body type of each place the handler answers
+    responses: Vec<(u16, Option<syn::Type>)>,
+    /// First type serialized anywhere in the body
+    serialized: Option<syn::Type>,
+    /// `let x: T` / `let x = T { .. }` bindings seen so far
+    locals: Vec<(String, syn::Type)>,
+    /// `let body = serde_json::to_string(&x)` bindings, with the type of `x`
+    serialized_locals: Vec<(String, syn::Type)>,
+    /// Type of the `let` being visited, for `let data: T = req.json()?`
+    pending_type: Option<syn::Type>,
+}
+
+impl BodyFacts {
+    /// Type of a value about to be serialized: `&response`, `&T { .. }`, `&vec![T { .. }]`
+    fn value_type(&self, expr: &syn::Expr) -> Option<syn::Type> {
+        match expr {
+            syn::Expr::Reference(r) => self.value_type(&r.expr),
+            syn::Expr::Paren(p) => self.value_type(&p.expr),
+            syn::Expr::Struct(s) => Some(syn::Type::Path(syn::TypePath { qself: None, path: s.path.clone() })),
+            syn::Expr::Path(p) => {
+                let name = p.path.get_ident()?.to_string();
+                self.locals.iter().rev().find(|(n, _)| *n == name).map(|(_, ty)| ty.clone())
+            }
+            syn::Expr::Macro(m) if m.mac.path.is_ident("vec") => {
+                let elems = m.mac.parse_body_with(syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated).ok()?;
+                let item = self.value_type(elems.first()?)?;
+                Some(syn::parse_quote!(Vec<#item>))
+            }
+            _ => None,
+        }
+    }
+
+    /// Type serialized by an expression: `serde_json::to_string(&x).unwrap()`,
+    /// `ResponseBuilder::new().json(&x).build()`, `Json(x)`, or a local holding one
+    fn serialized_type(&self, expr: &syn::Expr) -> Option<syn::Type> {
+        match expr {
+            syn::Expr::MethodCall(call) if call.method == "json" && call.args.len() == 1 => self.value_type(&call.args[0]),
+            syn::Expr::MethodCall(call) => self.serialized_type(&call.receiver),
+            syn::Expr::Call(call) if is_serialize_call(call) => call.args.first().and_then(|arg| self.value_type(arg)),
+            // `Json(x)`, also as `Ok(Json(x))` / `Some(Json(x))`
+            syn::Expr::Call(call) if call.args.len() == 1 && matches!(&*call.func, syn::Expr::Path(p) if p.path.is_ident("Json")) => {
+                self.value_type(&call.args[0])
+            }
+            syn::Expr::Call(call) if call.args.len() == 1 && matches!(&*call.func, syn::Expr::Path(p) if p.path.is_ident("Ok") || p.path.is_ident("Some")) => {
+                self.serialized_type(&call.args[0])
+            }
+            syn::Expr::Try(t) => self.serialized_type(&t.expr),
+            syn::Expr::Paren(p) => self.serialized_type(&p.expr),
+            syn::Expr::Path(p) => {
+                let name = p.path.get_ident()?.to_string();
+                self.serialized_locals.iter().rev().find(|(n, _)| *n == name).map(|(_, ty)| ty.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `serde_json::to_string(..)` and friends, or `responses::json(..)`
+fn is_serialize_call(call: &syn::ExprCall) -> bool {
+    let syn::Expr::Path(p) = &*call.func else { return false };
+    let segments: Vec<String> = p.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    matches!(
+        segments.iter().map(String::as_str).collect::<Vec<_>>().as_slice(),
+        [.., "serde_json", "to_string" | "to_string_pretty" | "to_vec" | "to_vec_pretty"] | [.., "responses", "json"]
+    )
+}
+
+/// `.status(401)` anywhere in a builder chain
+fn chain_status(expr: &syn::Expr) -> Option<u16> {
+    match expr {
+        syn::Expr::MethodCall(call) if call.method == "status" => call.args.first().and_then(int_literal),
+        syn::Expr::MethodCall(call) => chain_status(&call.receiver),
+        _ => None,
+    }
+}
+
+fn generic_type(args: &syn::PathArguments) -> Option<syn::Type> {
+    match args {
+        syn::PathArguments::AngleBracketed(a) => match a.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => Some(ty.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl<'ast> Visit<'ast> for BodyFacts {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        let (pat, ty) = match &local.pat {
+            syn::Pat::Type(pt) => (&*pt.pat, Some((*pt.ty).clone())),
+            pat => (pat, None),
+        };
+        let init = local.init.as_ref().map(|init| &*init.expr);
+        if let syn::Pat::Ident(ident) = pat {
+            let name = ident.ident.to_string();
+            if let Some(serialized) = init.and_then(|e| self.serialized_type(e)) {
+                self.serialized_locals.push((name, serialized));
+            } else if let Some(ty) = ty.clone().or_else(|| init.and_then(|e| self.value_type(e))) {
+                self.locals.push((name, ty));
+            }
+        }
+        let outer = std::mem::replace(&mut self.pending_type, ty);
+        syn::visit::visit_local(self, local);
+        self.pending_type = outer;
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let turbofish = call.turbofish.as_ref().and_then(|t| match t.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => Some(ty.clone()),
+            _ => None,
+        });
+        match call.method.to_string().as_str() {
+            // `req.json::<T>()` / `req.form::<T>()`
+            "json" | "form" if call.args.is_empty() && self.request.is_none() => {
+                self.request = turbofish.or_else(|| self.pending_type.clone());
+            }
+            // `ResponseBuilder::new().json(&value)`
+            "json" if call.args.len() == 1 && self.serialized.is_none() => self.serialized = self.value_type(&call.args[0]),
+            // The end of a `ResponseBuilder` chain is one answer
+            "build" => {
+                let expr = syn::Expr::MethodCall(call.clone());
+                self.responses.push((chain_status(&expr).unwrap_or(200), self.serialized_type(&expr)));
+            }
+            _ => {}
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(p) = &*call.func {
+            let segments: Vec<String> = p.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            match segments.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                // `serde_json::from_str::<T>(..)`
+                [.., "from_str" | "from_slice" | "from_value"] if self.request.is_none() => {
+                    let turbofish = p.path.segments.last().and_then(|s| generic_type(&s.arguments));
+                    self.request = turbofish.or_else(|| self.pending_type.clone());
+                }
+                // `responses::*` helpers answer with a fixed status
+                [.., "responses", helper] => {
+                    let status = match *helper {
+                        "not_found" => 404,
+                        "internal_error" => 500,
+                        "redirect" => 302,
+                        _ => 200,
+                    };
+                    let ty = if *helper == "json" { call.args.first().and_then(|arg| self.value_type(arg)) } else { None };
+                    if self.serialized.is_none() {
+                        self.serialized = ty.clone();
+                    }
+                    self.responses.push((status, ty));
+                }
+                _ if is_serialize_call(call) && self.serialized.is_none() => {
+                    self.serialized = call.args.first().and_then(|arg| self.value_type(arg));
+                }
+                _ => {}
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_tuple(&mut self, tuple: &'ast syn::ExprTuple) {
+        // `(body, 201)` as returned by `-> (String, u16)` handlers
+        if tuple.elems.len() == 2 && let Some(status) = int_literal(&tuple.elems[1]) {
+            self.responses.push((status, self.serialized_type(&tuple.elems[0])));
+        }
+        syn::visit::visit_expr_tuple(self, tuple);
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast syn::ExprMatch) {
+        // `match serde_json::to_string(&users) { Ok(json) => (json, 200), .. }`
+        if let Some(ty) = self.serialized_type(&expr.expr) {
+            for arm in &expr.arms {
+                if let syn::Pat::TupleStruct(ts) = &arm.pat
+                    && ts.path.is_ident("Ok")
+                    && let Some(syn::Pat::Ident(ident)) = ts.elems.first()
+                {
+                    self.serialized_locals.push((ident.ident.to_string(), ty.clone()));
+                }
+            }
+        }
+        syn::visit::visit_expr_match(self, expr);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        // `Response { status: 204, .. }`
+        if last_ident(&expr.path).as_deref() == Some("Response") {
+            let status = expr.fields.iter().find(|f| matches!(&f.member, syn::Member::Named(m) if m == "status"));
+            if let Some(status) = status.and_then(|f| int_literal(&f.expr)) {
+                self.responses.push((status, None));
+            }
+        }
+        syn::visit::visit_expr_struct(self, expr);
+    }
+
+    // Nested fns and impls aren't part of the handler
+    fn visit_item(&mut self, _item: &'ast syn::Item) {}
+}
+
+fn int_literal(expr: &syn::Expr) -> Option<u16> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse::<u16>().ok().filter(|s| (100..600).contains(s)),
+        _ => None,
+    }
+}
+
+fn last_ident(path: &syn::Path) -> Option<String> {
+    path.segments.last().map(|s| s.ident.to_string())
+}
+
+/// Serde-derived structs and enums defined at the top level of a route file
+struct LocalTypes<'ast> {
+    items: Vec<(String, &'ast syn::Item)>,
+}
+
+impl<'ast> LocalTypes<'ast> {
+    fn collect(ast: &'ast syn::File) -> Self {
+        let items = ast.items.iter().filter_map(|item| {
+            let (ident, attrs) = match item {
+                syn::Item::Struct(s) => (&s.ident, &s.attrs),
+                syn::Item::Enum(e) => (&e.ident, &e.attrs),
+                _ => return None,
+            };
+            let derives_serde = attrs.iter().filter(|a| a.path().is_ident("derive")).any(|a| {
+                a.meta.require_list().is_ok_and(|l| {
+                    let tokens = l.tokens.to_string();
+                    tokens.contains("Serialize") || tokens.contains("Deserialize")
+                })
+            });
+            derives_serde.then(|| (ident.to_string(), item))
+        }).collect();
+        LocalTypes { items }
+    }
+
+    fn get(&self, name: &str) -> Option<&'ast syn::Item> {
+        self.items.iter().find(|(n, _)| n == name).map(|(_, item)| *item)
+    }
+}
+
+/// `components.schemas`. A type name already taken by a different schema (from another route
+/// file) gets a numeric suffix: `Item`, `Item2`, ...
+#[derive(Default)]
+struct Components {
+    schemas: Map<String, Value>,
+}
+
+impl Components {
+    /// Schema of a request or response type, adding the local types it refers to. `None` when
+    /// nothing is known about it.
+    fn schema(&mut self, ty: &syn::Type, types: &LocalTypes) -> Option<Value> {
+        let schema = self.type_schema(ty, types, &mut Vec::new()).0;
+        (schema != json!({})).then_some(schema)
+    }
+
+    /// One query parameter per field of a `Query<T>` struct defined in the route file
+    fn query_parameters(&mut self, ty: &syn::Type, types: &LocalTypes, required: bool) -> Vec<Value> {
+        let syn::Type::Path(p) = ty else { return Vec::new() };
+        let Some(item) = last_ident(&p.path).and_then(|name| types.get(&name)) else { return Vec::new() };
+        let schema = self.item_schema(item, types, &mut Vec::new());
+        let Some(properties) = schema["properties"].as_object() else { return Vec::new() };
+        let required_fields = schema["required"].as_array().cloned().unwrap_or_default();
+        properties.iter().map(|(name, schema)| json!({
+            "name": name,
+            "in": "query",
+            "required": required && required_fields.contains(&Value::String(name.clone())),
+            "schema": schema,
+        })).collect()
+    }
+
+    /// `in_progress` holds the (type, key) pairs being described, so recursive types can refer to themselves
+    fn add_item(&mut self, name: &str, types: &LocalTypes, in_progress: &mut Vec<(String, String)>) -> Value {
+        let reference = |key: &str| json!({ "$ref": format!("#/components/schemas/{}", key) });
+        if let Some((_, key)) = in_progress.iter().find(|(n, _)| n == name) {
+            return reference(key);
+        }
+        let Some(item) = types.get(name) else { return json!({}) };
+        let mut key = name.to_string();
+        let mut n = 1;
+        while self.schemas.contains_key(&key) {
+            n += 1;
+            key = format!("{}{}", name, n);
+        }
+        in_progress.push((name.to_string(), key.clone()));
+        let schema = self.item_schema(item, types, in_progress);
+        in_progress.pop();
+        // The same type seen before (another handler of the file, or an identical definition elsewhere)
+        let existing = std::iter::once(name.to_string())
+            .chain((2..n).map(|i| format!("{}{}", name, i)))
+            .find(|k| self.schemas.get(k) == Some(&schema));
+        match existing {
+            Some(existing) => reference(&existing),
+            None => {
+                self.schemas.insert(key.clone(), schema);
+                reference(&key)
+            }
+        }
+    }
+
+    fn item_schema(&mut self, item: &syn::Item, types: &LocalTypes, in_progress: &mut Vec<(String, String)>) -> Value {
+        match item {
+            syn::Item::Struct(s) => {
+                let rename_all = serde_attr(&s.attrs, "rename_all");
+                match &s.fields {
+                    syn::Fields::Named(fields) => self.object_schema(&fields.named, rename_all.as_deref(), types, in_progress),
+                    syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        self.type_schema(&fields.unnamed[0].ty, types, in_progress).0
+                    }
+                    syn::Fields::Unnamed(fields) => {
+                        let items: Vec<Value> = fields.unnamed.iter().map(|f| self.type_schema(&f.ty, types, in_progress).0).collect();
+                        json!({ "type": "array", "prefixItems": items, "items": false })
+                    }
+                    syn::Fields::Unit => json!({ "type": "null" }),
+                }
+            }
+            syn::Item::Enum(e) => {
+                let rename_all = serde_attr(&e.attrs, "rename_all");
+                let variant_name = |v: &syn::Variant| {
+                    serde_attr(&v.attrs, "rename").unwrap_or_else(|| rename(&v.ident.to_string(), rename_all.as_deref()))
+                };
+                if e.variants.iter().all(|v| matches!(v.fields, syn::Fields::Unit)) {
+                    let names: Vec<String> = e.variants.iter().map(variant_name).collect();
+                    return json!({ "type": "string", "enum": names });
+                }
+                // Externally tagged, serde's default: `"Unit"` or `{"Variant": ..}`
+                let variants: Vec<Value> = e.variants.iter().map(|v| {
+                    let name = variant_name(v);
+                    let inner = match &v.fields {
+                        syn::Fields::Unit => return json!({ "const": name }),
+                        syn::Fields::Named(fields) => self.object_schema(&fields.named, None, types, in_progress),
+                        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => self.type_schema(&fields.unnamed[0].ty, types, in_progress).0,
+                        syn::Fields::Unnamed(fields) => {
+                            let items: Vec<Value> = fields.unnamed.iter().map(|f| self.type_schema(&f.ty, types, in_progress).0).collect();
+                            json!({ "type": "array", "prefixItems": items, "items": false })
+                        }
+                    };
+                    json!({ "type": "object", "properties": { name.clone(): inner }, "required": [name], "additionalProperties": false })
+                }).collect();
+                json!({ "oneOf": variants })
+            }
+            _ => json!({}),
+        }
+    }
+
+    fn object_schema(
+        &mut self,
+        fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+        rename_all: Option<&str>,
+        types: &LocalTypes,
+        in_progress: &mut Vec<(String, String)>,
+    ) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            let Some(ident) = &field.ident else { continue };
+            if serde_flag(&field.attrs, "skip") || serde_flag(&field.attrs, "skip_serializing") || serde_flag(&field.attrs, "skip_deserializing") {
+                continue;
+            }
+            let name = serde_attr(&field.attrs, "rename").unwrap_or_else(|| rename(&ident.to_string(), rename_all));
+            let (schema, optional) = self.type_schema(&field.ty, types, in_progress);
+            if !optional && !serde_flag(&field.attrs, "default") {
+                required.push(Value::String(name.clone()));
+            }
+            properties.insert(name, schema);
+        }
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = Value::Array(required);
+        }
+        schema
+    }
+
+    /// Schema of a field type, and whether the field may be left out (`Option<T>`)
+    fn type_schema(&mut self, ty: &syn::Type, types: &LocalTypes, in_progress: &mut Vec<(String, String)>) -> (Value, bool) {
+        match ty {
+            syn::Type::Reference(r) => self.type_schema(&r.elem, types, in_progress),
+            syn::Type::Paren(p) => self.type_schema(&p.elem, types, in_progress),
+            syn::Type::Slice(s) => (json!({ "type": "array", "items": self.type_schema(&s.elem, types, in_progress).0 }), false),
+            syn::Type::Array(a) => (json!({ "type": "array", "items": self.type_schema(&a.elem, types, in_progress).0 }), false),
+            syn::Type::Tuple(t) if t.elems.is_empty() => (json!({ "type": "null" }), false),
+            syn::Type::Tuple(t) => {
+                let items: Vec<Value> = t.elems.iter().map(|e| self.type_schema(e, types, in_progress).0).collect();
+                (json!({ "type": "array", "prefixItems": items, "items": false }), false)
+            }
+            syn::Type::Path(p) if p.qself.is_none() => {
+                let Some(last) = p.path.segments.last() else { return (json!({}), false) };
+                let name = last.ident.to_string();
+                let args: Vec<&syn::Type> = match &last.arguments {
+                    syn::PathArguments::AngleBracketed(a) => a.args.iter().filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    }).collect(),
+                    _ => Vec::new(),
+                };
+                if let Some(schema) = primitive_schema(&name) {
+                    return (schema, false);
+                }
+                match (name.as_str(), args.as_slice()) {
+                    ("Option", [inner]) => (self.type_schema(inner, types, in_progress).0, true),
+                    ("Box" | "Arc" | "Rc" | "Cow", [.., inner]) => self.type_schema(inner, types, in_progress),
+                    ("Vec" | "VecDeque" | "HashSet" | "BTreeSet" | "SmallVec", [inner, ..]) => {
+                        let mut schema = json!({ "type": "array", "items": self.type_schema(inner, types, in_progress).0 });
+                        if name.ends_with("Set") {
+                            schema["uniqueItems"] = true.into();
+                        }
+                        (schema, false)
+                    }
+                    ("HashMap" | "BTreeMap" | "AHashMap", [_, value, ..]) => {
+                        (json!({ "type": "object", "additionalProperties": self.type_schema(value, types, in_progress).0 }), false)
+                    }
+                    ("DateTime" | "NaiveDateTime" | "SystemTime", _) => (json!({ "type": "string", "format": "date-time" }), false),
+                    ("NaiveDate", _) => (json!({ "type": "string", "format": "date" }), false),
+                    ("Uuid", _) => (json!({ "type": "string", "format": "uuid" }), false),
+                    _ if types.get(&name).is_some() => (self.add_item(&name, types, in_progress), false),
+                    // `serde_json::Value` and types defined elsewhere
+                    _ => (json!({}), false),
+                }
+            }
+            _ => (json!({}), false),
+        }
+    }
+}
+
+/// Schema of a primitive, also used for `[id:u64]` params
+fn primitive_schema(name: &str) -> Option<Value> {
+    Some(match name {
+        "String" | "str" | "char" => json!({ "type": "string" }),
+        "bool" => json!({ "type": "boolean" }),
+        "i8" | "i16" | "i32" => json!({ "type": "integer", "format": "int32" }),
+        "i64" | "isize" => json!({ "type": "integer", "format": "int64" }),
+        "i128" => json!({ "type": "integer" }),
+        "u8" | "u16" | "u32" => json!({ "type": "integer", "format": "int32", "minimum": 0 }),
+        "u64" | "usize" => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+        "u128" => json!({ "type": "integer", "minimum": 0 }),
+        "f32" => json!({ "type": "number", "format": "float" }),
+        "f64" => json!({ "type": "number", "format": "double" }),
+        _ => return None,
+    })
+}
+
+/// `#[serde(key = "value")]`
+fn serde_attr(attrs: &[syn::Attribute], key: &str) -> Option<String> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                if let Ok(value) = meta.value() {
+                    found = value.parse::<syn::LitStr>().ok().map(|s| s.value());
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                // skip the value of other `key = ..` entries
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// `#[serde(flag)]` or `#[serde(flag = ..)]`
+fn serde_flag(attrs: &[syn::Attribute], flag: &str) -> bool {
+    let mut found = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(flag) {
+                found = true;
+            }
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// Apply a serde `rename_all` rule to a field or variant name
+fn rename(name: &str, rule: Option<&str>) -> String {
+    // Words of either `snake_case` fields or `PascalCase` variants
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let mut word = String::new();
+        for c in part.chars() {
+            if c.is_uppercase() && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c);
+        }
+        words.push(word);
+    }
+    let capitalize = |w: &String| {
+        let mut chars = w.chars();
+        chars.next().map_or(String::new(), |c| c.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase())
+    };
+    match rule {
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") => name.to_uppercase(),
+        Some("PascalCase") => words.iter().map(capitalize).collect(),
+        Some("camelCase") => {
+            let pascal: String = words.iter().map(capitalize).collect();
+            let mut chars = pascal.chars();
+            chars.next().map_or(String::new(), |c| c.to_lowercase().collect::<String>() + chars.as_str())
+        }
+        Some("snake_case") => words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("_"),
+        Some("SCREAMING_SNAKE_CASE") => words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("_"),
+        Some("kebab-case") => words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("-"),
+        Some("SCREAMING-KEBAB-CASE") => words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("-"),
+        _ => name.to_string(),
+    }
+}
@@ -0,0 +1,289 @@
+// `const ROUTE: RouteConfig = RouteConfig { .. }` of a route file (see `rsf::engine::route_config`).
+//
+// rustc type-checks the const where it is inlined; this reads its values so they can be
+// validated up front and written to the route manifest and the OpenAPI document. Every field
+// therefore has to be spelled out as a constant build.rs can evaluate: literals, `*` / `+`,
+// `Duration::from_secs(..)` / `from_millis(..)`, `RateLimit::per_minute(..)` and so on.
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+/// Values of a route file's `ROUTE` const, or the defaults when it has none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMeta {
+    /// The file declares `ROUTE` (the generated table refers to it instead of the default)
+    pub declared: bool,
+    /// "api" or "ui"
+    pub kind: &'static str,
+    /// "none", "bearer" or "basic"
+    pub auth: &'static str,
+    pub max_body: Option<u64>,
+    pub timeout_ms: Option<u64>,
+    /// (requests, window in milliseconds)
+    pub rate_limit: Option<(u64, u64)>,
+    pub cache_ttl_ms: Option<u64>,
+    pub tags: Vec<String>,
+}
+
+impl Default for RouteMeta {
+    fn default() -> Self {
+        RouteMeta { declared: false, kind: "api", auth: "none", max_body: None, timeout_ms: None, rate_limit: None, cache_ttl_ms: None, tags: Vec::new() }
+    }
+}
+
+impl RouteMeta {
+    /// Manifest entry for the settings
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "auth": self.auth,
+            "max_body": self.max_body,
+            "timeout_ms": self.timeout_ms,
+            "rate_limit": self.rate_limit.map(|(requests, per_ms)| serde_json::json!({ "requests": requests, "per_ms": per_ms })),
+            "cache_ttl_ms": self.cache_ttl_ms,
+            "tags": self.tags,
+        })
+    }
+}
+
+const FIELDS: [&str; 7] = ["kind", "auth", "max_body", "timeout", "rate_limit", "cache_ttl", "tags"];
+
+/// Read the `ROUTE` item of a parsed route file. Errors carry the span to report them at.
+pub fn read(ast: &syn::File) -> Result<RouteMeta, Vec<(Span, String)>> {
+    let mut meta = RouteMeta::default();
+    let mut errors = Vec::new();
+    let mut found = false;
+    for item in &ast.items {
+        let (ident, ty, expr) = match item {
+            syn::Item::Const(c) => (&c.ident, &c.ty, &c.expr),
+            syn::Item::Static(s) if s.ident == "ROUTE" => {
+                errors.push((s.static_token.span(), "`ROUTE` must be a `const`, not a `static`".to_string()));
+                continue;
+            }
+            _ => continue,
+        };
+        if ident != "ROUTE" {
+            continue;
+        }
+        found = true;
+        if !super::type_is(ty, "RouteConfig") {
+            errors.push((ty.span(), "`ROUTE` must have type `RouteConfig`".to_string()));
+            continue;
+        }
+        let syn::Expr::Struct(lit) = &**expr else {
+            errors.push((expr.span(), "`ROUTE` must be a `RouteConfig { .. }` literal".to_string()));
+            continue;
+        };
+        if let Some(rest) = &lit.rest && !is_path(rest, &["RouteConfig", "DEFAULT"]) {
+            errors.push((rest.span(), "only `..RouteConfig::DEFAULT` can fill in the remaining fields".to_string()));
+        }
+        for field in &lit.fields {
+            let syn::Member::Named(name) = &field.member else { continue };
+            let name = name.to_string();
+            if let Err(err) = read_field(&mut meta, &name, &field.expr) {
+                errors.push(err);
+            }
+        }
+    }
+    meta.declared = found;
+    if errors.is_empty() { Ok(meta) } else { Err(errors) }
+}
+
+fn read_field(meta: &mut RouteMeta, name: &str, expr: &syn::Expr) -> Result<(), (Span, String)> {
+    match name {
+        "kind" => {
+            meta.kind = match variant(expr, "RouteKind").as_deref() {
+                Some("Api") => "api",
+                Some("Ui") => "ui",
+                _ => return Err((expr.span(), "`kind` must be `RouteKind::Api` or `RouteKind::Ui`".to_string())),
+            };
+        }
+        "auth" => {
+            meta.auth = match variant(expr, "Auth").as_deref() {
+                Some("None") => "none",
+                Some("Bearer") => "bearer",
+                Some("Basic") => "basic",
+                _ => return Err((expr.span(), "`auth` must be `Auth::None`, `Auth::Bearer` or `Auth::Basic`".to_string())),
+            };
+        }
+        "max_body" => meta.max_body = optional(expr, |e| positive(e, "max_body", integer(e)))?,
+        "timeout" => meta.timeout_ms = optional(expr, |e| positive(e, "timeout", duration_ms(e)))?,
+        "cache_ttl" => meta.cache_ttl_ms = optional(expr, |e| positive(e, "cache_ttl", duration_ms(e)))?,
+        "rate_limit" => meta.rate_limit = optional(expr, rate_limit)?,
+        "tags" => meta.tags = tags(expr)?,
+        _ => return Err((expr.span(), format!("unknown `RouteConfig` field `{}`, expected one of {}", name, FIELDS.join(", ")))),
+    }
+    Ok(())
+}
+
+/// `None` or `Some(value)`
+fn optional<T>(expr: &syn::Expr, read: impl Fn(&syn::Expr) -> Result<T, (Span, String)>) -> Result<Option<T>, (Span, String)> {
+    match expr {
+        syn::Expr::Path(p) if p.path.is_ident("None") => Ok(None),
+        syn::Expr::Call(call) if is_path(&call.func, &["Some"]) && call.args.len() == 1 => read(&call.args[0]).map(Some),
+        syn::Expr::Paren(p) => optional(&p.expr, read),
+        _ => Err((expr.span(), "expected `None` or `Some(..)`".to_string())),
+    }
+}
+
+fn positive(expr: &syn::Expr, field: &str, value: Option<u64>) -> Result<u64, (Span, String)> {
+    match value {
+        Some(0) => Err((expr.span(), format!("`{}` must be greater than zero", field))),
+        Some(v) => Ok(v),
+        None => Err((expr.span(), format!("`{}` must be a constant build.rs can evaluate", field))),
+    }
+}
+
+/// Integer literals combined with `*` and `+`, e.g. `10 * 1024 * 1024`
+fn integer(expr: &syn::Expr) -> Option<u64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse().ok(),
+        syn::Expr::Paren(p) => integer(&p.expr),
+        syn::Expr::Binary(b) => {
+            let (l, r) = (integer(&b.left)?, integer(&b.right)?);
+            match b.op {
+                syn::BinOp::Mul(_) => l.checked_mul(r),
+                syn::BinOp::Add(_) => l.checked_add(r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `Duration::from_secs(n)` / `from_millis(n)`, in milliseconds
+fn duration_ms(expr: &syn::Expr) -> Option<u64> {
+    let syn::Expr::Call(call) = expr else { return None };
+    let syn::Expr::Path(func) = &*call.func else { return None };
+    let segs: Vec<String> = func.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    if call.args.len() != 1 || segs.len() < 2 || segs[segs.len() - 2] != "Duration" {
+        return None;
+    }
+    let n = integer(&call.args[0])?;
+    match segs[segs.len() - 1].as_str() {
+        "from_secs" => n.checked_mul(1000),
+        "from_millis" => Some(n),
+        _ => None,
+    }
+}
+
+/// `RateLimit::per_second(n)` / `per_minute(n)` / `per_hour(n)` or `RateLimit { requests, per }`
+fn rate_limit(expr: &syn::Expr) -> Result<(u64, u64), (Span, String)> {
+    let invalid = || (expr.span(), "`rate_limit` must be `RateLimit::per_second(n)`, `per_minute(n)`, `per_hour(n)` or `RateLimit { requests, per }`".to_string());
+    let (requests, per_ms) = match expr {
+        syn::Expr::Call(call) if call.args.len() == 1 => {
+            let window = match variant(&call.func, "RateLimit").as_deref() {
+                Some("per_second") => 1_000,
+                Some("per_minute") => 60_000,
+                Some("per_hour") => 3_600_000,
+                _ => return Err(invalid()),
+            };
+            (integer(&call.args[0]), Some(window))
+        }
+        syn::Expr::Struct(s) if s.path.is_ident("RateLimit") && s.rest.is_none() => {
+            let field = |name: &str| s.fields.iter().find(|f| matches!(&f.member, syn::Member::Named(n) if n == name)).map(|f| &f.expr);
+            (field("requests").and_then(integer), field("per").and_then(duration_ms))
+        }
+        _ => return Err(invalid()),
+    };
+    match (requests, per_ms) {
+        (Some(0), _) | (_, Some(0)) => Err((expr.span(), "`rate_limit` must allow at least one request per non-empty window".to_string())),
+        (Some(requests), Some(per_ms)) if requests <= u32::MAX as u64 => Ok((requests, per_ms)),
+        _ => Err(invalid()),
+    }
+}
+
+/// `&["a", "b"]`: non-empty and distinct
+fn tags(expr: &syn::Expr) -> Result<Vec<String>, (Span, String)> {
+    let invalid = || (expr.span(), "`tags` must be a list of string literals, e.g. `&[\"users\"]`".to_string());
+    let syn::Expr::Reference(r) = expr else { return Err(invalid()) };
+    let syn::Expr::Array(arr) = &*r.expr else { return Err(invalid()) };
+    let mut tags = Vec::new();
+    for elem in &arr.elems {
+        let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) = elem else { return Err(invalid()) };
+        let tag = s.value();
+        if tag.trim().is_empty() {
+            return Err((elem.span(), "tags cannot be empty".to_string()));
+        }
+        if tags.contains(&tag) {
+            return Err((elem.span(), format!("duplicate tag `{}`", tag)));
+        }
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+/// `Enum::Variant` -> "Variant"
+fn variant(expr: &syn::Expr, enum_name: &str) -> Option<String> {
+    let syn::Expr::Path(p) = expr else { return None };
+    let segs = &p.path.segments;
+    if segs.len() < 2 || segs[segs.len() - 2].ident != enum_name {
+        return None;
+    }
+    Some(segs[segs.len() - 1].ident.to_string())
+}
+
+/// True when `expr` is a path ending in `names`, e.g. `crate::engine::RouteConfig::DEFAULT`
+fn is_path(expr: &syn::Expr, names: &[&str]) -> bool {
+    let syn::Expr::Path(p) = expr else { return false };
+    let segs = &p.path.segments;
+    segs.len() >= names.len() && segs.iter().skip(segs.len() - names.len()).zip(names).all(|(s, n)| s.ident == *n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_src(src: &str) -> Result<RouteMeta, Vec<String>> {
+        read(&syn::parse_file(src).unwrap()).map_err(|errs| errs.into_iter().map(|(_, msg)| msg).collect())
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(read_src("pub fn get() {}"), Ok(RouteMeta::default()));
+        let meta = read_src(r#"
+            pub const ROUTE: RouteConfig = RouteConfig {
+                kind: RouteKind::Ui,
+                auth: rsf::Auth::Bearer,
+                max_body: Some(10 * 1024 * 1024),
+                timeout: Some(Duration::from_secs(30)),
+                rate_limit: Some(RateLimit { requests: 5, per: Duration::from_millis(500) }),
+                cache_ttl: None,
+                tags: &["users", "admin"],
+                ..RouteConfig::DEFAULT
+            };
+        "#).unwrap();
+        assert_eq!(meta, RouteMeta {
+            declared: true,
+            kind: "ui",
+            auth: "bearer",
+            max_body: Some(10 * 1024 * 1024),
+            timeout_ms: Some(30_000),
+            rate_limit: Some((5, 500)),
+            cache_ttl_ms: None,
+            tags: vec!["users".to_string(), "admin".to_string()],
+        });
+        assert_eq!(read_src("pub const ROUTE: RouteConfig = RouteConfig { rate_limit: Some(RateLimit::per_minute(60)), ..RouteConfig::DEFAULT };").unwrap().rate_limit, Some((60, 60_000)));
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let errors = read_src(r#"
+            pub const ROUTE: RouteConfig = RouteConfig {
+                max_body: Some(0),
+                timeout: Some(Duration::from_secs(LIMIT)),
+                tags: &["a", "a"],
+                retries: 3,
+                ..OTHER
+            };
+        "#).unwrap_err();
+        assert_eq!(errors, [
+            "only `..RouteConfig::DEFAULT` can fill in the remaining fields",
+            "`max_body` must be greater than zero",
+            "`timeout` must be a constant build.rs can evaluate",
+            "duplicate tag `a`",
+            "unknown `RouteConfig` field `retries`, expected one of kind, auth, max_body, timeout, rate_limit, cache_ttl, tags",
+        ]);
+        assert_eq!(read_src("pub static ROUTE: RouteConfig = RouteConfig::DEFAULT;").unwrap_err(), ["`ROUTE` must be a `const`, not a `static`"]);
+        assert_eq!(read_src("pub const ROUTE: u32 = 1;").unwrap_err(), ["`ROUTE` must have type `RouteConfig`"]);
+    }
+}
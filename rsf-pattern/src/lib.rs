@@ -0,0 +1,296 @@
+//! The route pattern grammar shared by `rsf` and `rsf-build`: the segments of a pattern such as
+//! `/users/[id:u64]/files/[...path]`, the checks a pattern has to pass and the rules that decide
+//! whether two routes can be served side by side.
+//!
+//! `rsf-build` applies them to route files at build time, `rsf` to the routes of a `Router`
+//! at startup, so both report the same errors for the same patterns.
+
+/// Methods a route can have a handler for
+pub const METHODS: [&str; 7] = ["GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "HEAD"];
+
+/// Types allowed in `[name:type]` segments
+pub const PARAM_TYPES: [&str; 15] = [
+    "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize",
+    "f32", "f64", "bool",
+];
+
+/// One segment of a route pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternSeg<'a> {
+    /// `users`
+    Static(&'a str),
+    /// `[id]`, `[id:u64]` or `[name:regex([a-z]+)]`
+    Param(&'a str, Option<ParamSpec<'a>>),
+    /// `[...slug]` - one or more remaining segments
+    CatchAll(&'a str),
+    /// `[[...slug]]` - zero or more remaining segments
+    OptionalCatchAll(&'a str),
+}
+
+/// Constraint on a dynamic segment; segments that don't satisfy it fall through to other routes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamSpec<'a> {
+    /// `[id:u64]` - must parse as the given primitive type
+    Type(&'a str),
+    /// `[name:regex(..)]` - must match the whole pattern
+    Regex(&'a str),
+}
+
+impl<'a> ParamSpec<'a> {
+    /// Rust type handlers receive the param as
+    pub fn rust_type(self) -> &'a str {
+        match self {
+            ParamSpec::Type(ty) => ty,
+            ParamSpec::Regex(_) => "String",
+        }
+    }
+}
+
+/// Split a route like "/docs/[...slug]" into pattern segments.
+pub fn route_pattern(route: &str) -> Vec<PatternSeg<'_>> {
+    let route = route.trim_start_matches('/').trim_end_matches('/');
+    if route.is_empty() {
+        return Vec::new();
+    }
+    route.split('/').map(pattern_segment).collect()
+}
+
+pub fn pattern_segment(seg: &str) -> PatternSeg<'_> {
+    if let Some(name) = seg.strip_prefix("[[...").and_then(|s| s.strip_suffix("]]")) {
+        PatternSeg::OptionalCatchAll(name)
+    } else if let Some(name) = seg.strip_prefix("[...").and_then(|s| s.strip_suffix(']')) {
+        PatternSeg::CatchAll(name)
+    } else if let Some(inner) = seg.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        match inner.split_once(':') {
+            Some((name, spec)) => match spec.strip_prefix("regex(").and_then(|s| s.strip_suffix(')')) {
+                Some(re) => PatternSeg::Param(name, Some(ParamSpec::Regex(re))),
+                None => PatternSeg::Param(name, Some(ParamSpec::Type(spec))),
+            },
+            None => PatternSeg::Param(inner, None),
+        }
+    } else {
+        PatternSeg::Static(seg)
+    }
+}
+
+/// Whether a decoded segment parses as one of [`PARAM_TYPES`]
+pub fn type_accepts(ty: &str, value: &str) -> bool {
+    match ty {
+        "u8" => value.parse::<u8>().is_ok(),
+        "u16" => value.parse::<u16>().is_ok(),
+        "u32" => value.parse::<u32>().is_ok(),
+        "u64" => value.parse::<u64>().is_ok(),
+        "u128" => value.parse::<u128>().is_ok(),
+        "usize" => value.parse::<usize>().is_ok(),
+        "i8" => value.parse::<i8>().is_ok(),
+        "i16" => value.parse::<i16>().is_ok(),
+        "i32" => value.parse::<i32>().is_ok(),
+        "i64" => value.parse::<i64>().is_ok(),
+        "i128" => value.parse::<i128>().is_ok(),
+        "isize" => value.parse::<isize>().is_ok(),
+        "f32" => value.parse::<f32>().is_ok(),
+        "f64" => value.parse::<f64>().is_ok(),
+        "bool" => value.parse::<bool>().is_ok(),
+        _ => false,
+    }
+}
+
+/// Check the segments of a single route pattern: catch-alls must come last and param
+/// names must be unique identifiers.
+pub fn validate_pattern(pattern: &[PatternSeg]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut names: Vec<&str> = Vec::new();
+    for (i, seg) in pattern.iter().enumerate() {
+        let name = match seg {
+            PatternSeg::Static(s) => {
+                if s.contains('[') || s.contains(']') {
+                    errors.push(format!("malformed segment `{}`", s));
+                }
+                continue;
+            }
+            PatternSeg::Param(name, spec) => {
+                match spec {
+                    Some(ParamSpec::Type(ty)) if !PARAM_TYPES.contains(ty) => {
+                        errors.push(format!("unsupported type `{}` for parameter `{}` (expected one of {})", ty, name, PARAM_TYPES.join(", ")));
+                    }
+                    Some(ParamSpec::Regex(re)) => {
+                        if let Err(e) = regex::Regex::new(re) {
+                            // regex errors span several lines; keep the summary on the last one
+                            let e = e.to_string();
+                            let summary = e.lines().last().unwrap_or_default().trim_start_matches("error: ");
+                            errors.push(format!("invalid regex for parameter `{}`: {}", name, summary));
+                        }
+                    }
+                    _ => {}
+                }
+                name
+            }
+            PatternSeg::CatchAll(name) | PatternSeg::OptionalCatchAll(name) => {
+                if i != pattern.len() - 1 {
+                    errors.push("catch-all segment must be the last segment".to_string());
+                }
+                name
+            }
+        };
+        if !is_valid_param_name(name) {
+            errors.push(format!("invalid parameter name `{}` (expected letters, digits or `_`, not starting with a digit)", name));
+        } else if names.contains(name) {
+            errors.push(format!("duplicate parameter name `{}`", name));
+        } else {
+            names.push(name);
+        }
+    }
+    errors
+}
+
+pub fn is_valid_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Shape of a pattern segment with param names erased, used to compare routes.
+/// Params with different constraints are distinct: `[id:u64]` falls through to `[slug]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegShape<'a> {
+    Static(&'a str),
+    Param(Option<ParamSpec<'a>>),
+    CatchAll,
+    OptionalCatchAll,
+}
+
+pub fn route_shape<'a>(pattern: &[PatternSeg<'a>]) -> Vec<SegShape<'a>> {
+    pattern.iter().map(|seg| match seg {
+        PatternSeg::Static(s) => SegShape::Static(s),
+        PatternSeg::Param(_, spec) => SegShape::Param(*spec),
+        PatternSeg::CatchAll(_) => SegShape::CatchAll,
+        PatternSeg::OptionalCatchAll(_) => SegShape::OptionalCatchAll,
+    }).collect()
+}
+
+/// Why routes `a` and `b` can't both be served: the same URL (e.g. `api.rs` and
+/// `api/index.rs`), the same shape with different param names (`users/[id]` and
+/// `users/[name]`), constraints that can match the same segment (`[id:u64]` and
+/// `[code:regex(\d+)]`), or an optional catch-all overlapping its parent route or a catch-all
+/// at the same level.
+///
+/// A constrained param next to a plain one is not a conflict: `[id:u64]` is tried first and
+/// everything it rejects falls through to `[slug]`.
+pub fn conflict(a: &str, b: &str) -> Option<String> {
+    let (sa, sb) = (route_shape(&route_pattern(a)), route_shape(&route_pattern(b)));
+    if a == b {
+        Some(format!("duplicate route {}", a))
+    } else if sa == sb {
+        Some(format!("ambiguous routes {} and {}", a, b))
+    } else if sa.len() == sb.len() && sa.iter().zip(&sb).all(|(x, y)| shapes_overlap(*x, *y)) {
+        Some(format!("ambiguous routes {} and {} (their param constraints can match the same segment)", a, b))
+    } else if optional_catch_all_overlaps(&sa, &sb) || optional_catch_all_overlaps(&sb, &sa) {
+        Some(format!("overlapping routes {} and {}", a, b))
+    } else {
+        None
+    }
+}
+
+/// Whether a segment can match both shapes with neither being tried first. Two regexes are
+/// taken to overlap; a type and a regex overlap unless every match of the regex starts with a
+/// character no value of the type starts with (`ord-[a-z0-9]+` next to `u64`).
+fn shapes_overlap(a: SegShape, b: SegShape) -> bool {
+    match (a, b) {
+        (SegShape::Param(Some(x)), SegShape::Param(Some(y))) => match (x, y) {
+            (ParamSpec::Regex(_), ParamSpec::Regex(_)) => true,
+            (ParamSpec::Type(ty), ParamSpec::Regex(re)) | (ParamSpec::Regex(re), ParamSpec::Type(ty)) => {
+                let first = type_first_chars(ty);
+                regex_prefixes(re).is_none_or(|prefixes| prefixes.iter().any(|p| p.first().is_none_or(|c| first.contains(c))))
+            }
+            // `0` parses as every number type
+            (ParamSpec::Type(x), ParamSpec::Type(y)) => (x == "bool") == (y == "bool"),
+        },
+        _ => a == b,
+    }
+}
+
+/// Characters a value of `ty` can start with
+fn type_first_chars(ty: &str) -> &'static [u8] {
+    match ty {
+        "bool" => b"tf",
+        "f32" | "f64" => b"0123456789+-.iInN",
+        t if t.starts_with('i') => b"0123456789+-",
+        _ => b"0123456789+",
+    }
+}
+
+/// Literal prefixes of every match of a regex, or `None` if there are too many to list
+fn regex_prefixes(re: &str) -> Option<Vec<Vec<u8>>> {
+    let hir = regex_syntax::parse(re).ok()?;
+    let mut extractor = regex_syntax::hir::literal::Extractor::new();
+    extractor.limit_class(128);
+    let seq = extractor.extract(&hir);
+    Some(seq.literals()?.iter().map(|lit| lit.as_bytes().to_vec()).collect())
+}
+
+/// `/docs/[[...slug]]` also matches `/docs` and everything `/docs/[...rest]` matches.
+fn optional_catch_all_overlaps(a: &[SegShape], b: &[SegShape]) -> bool {
+    let Some((SegShape::OptionalCatchAll, prefix)) = a.split_last() else {
+        return false;
+    };
+    b == prefix || (b.len() == a.len() && b.starts_with(prefix) && b.last() == Some(&SegShape::CatchAll))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_segments() {
+        assert_eq!(route_pattern("/"), []);
+        assert_eq!(
+            route_pattern("/users/[id:u64]/[slug:regex([a-z]+)]/[...rest]"),
+            [
+                PatternSeg::Static("users"),
+                PatternSeg::Param("id", Some(ParamSpec::Type("u64"))),
+                PatternSeg::Param("slug", Some(ParamSpec::Regex("[a-z]+"))),
+                PatternSeg::CatchAll("rest"),
+            ]
+        );
+        assert_eq!(pattern_segment("[[...slug]]"), PatternSeg::OptionalCatchAll("slug"));
+    }
+
+    #[test]
+    fn test_validate_pattern() {
+        assert!(validate_pattern(&route_pattern("/users/[id:u64]/[...rest]")).is_empty());
+        assert_eq!(validate_pattern(&route_pattern("/files/[...path]/raw")), ["catch-all segment must be the last segment"]);
+        assert_eq!(validate_pattern(&route_pattern("/a/[id]/b/[id:u8]")), ["duplicate parameter name `id`"]);
+        assert_eq!(validate_pattern(&route_pattern("/[1st]")), ["invalid parameter name `1st` (expected letters, digits or `_`, not starting with a digit)"]);
+        assert!(validate_pattern(&route_pattern("/[id:uuid]"))[0].starts_with("unsupported type `uuid`"));
+        assert!(validate_pattern(&route_pattern("/[id:regex(()]"))[0].starts_with("invalid regex for parameter `id`"));
+    }
+
+    #[test]
+    fn test_conflict() {
+        assert_eq!(conflict("/users/[id]", "/users/[id]").as_deref(), Some("duplicate route /users/[id]"));
+        assert_eq!(conflict("/users/[id]", "/users/[name]").as_deref(), Some("ambiguous routes /users/[id] and /users/[name]"));
+        assert_eq!(conflict("/docs/[[...slug]]", "/docs").as_deref(), Some("overlapping routes /docs/[[...slug]] and /docs"));
+        assert_eq!(conflict("/docs/[...rest]", "/docs/[[...slug]]").as_deref(), Some("overlapping routes /docs/[...rest] and /docs/[[...slug]]"));
+        assert_eq!(conflict("/users/[id:u64]", "/users/[name]"), None);
+        assert_eq!(conflict("/users/me", "/users/[id]"), None);
+    }
+
+    #[test]
+    fn test_constraint_overlaps() {
+        let ambiguous = |a, b| conflict(a, b).is_some_and(|e| e.ends_with("(their param constraints can match the same segment)"));
+        assert!(ambiguous("/orders/[id:u64]", r"/orders/[code:regex(\d+)]"));
+        assert!(ambiguous("/orders/[a:regex([a-z]+)]", "/orders/[b:regex([0-9]+)]"));
+        assert!(ambiguous("/orders/[id:u64]", "/orders/[n:i32]"));
+        assert!(ambiguous("/orders/[id:f64]", "/orders/[n:u8]"));
+        assert!(ambiguous("/flags/[on:bool]/[id:u64]", r"/flags/[b:regex(true|false)]/[n:u16]"));
+        assert!(ambiguous("/orders/[id:u64]", r"/orders/[code:regex(\w+)]"));
+        assert!(ambiguous("/orders/[id:u64]", r"/orders/[code:regex(x?\d+)]"));
+        assert_eq!(conflict("/orders/[id:u64]", "/orders/[code:regex(ord-[a-z0-9]+)]"), None);
+        assert_eq!(conflict("/orders/[id:i64]", "/orders/[name:regex([a-z]+)]"), None);
+        assert_eq!(conflict("/orders/[id:u64]", "/orders/[on:bool]"), None);
+        assert_eq!(conflict("/orders/[id:u64]/a", r"/orders/[code:regex(\d+)]/b"), None);
+        // A constrained param is tried before a plain one, which gets what it rejects
+        assert_eq!(conflict(r"/orders/[code:regex(\d+)]", "/orders/[slug]"), None);
+    }
+}